@@ -0,0 +1,122 @@
+// src/camera.rs
+use raylib::prelude::*;
+
+use crate::player::Player;
+
+// Limites del campo de vision horizontal (radianes)
+pub const MIN_FOV: f32 = 0.35; // ~20 grados
+pub const MAX_FOV: f32 = 2.1;  // ~120 grados
+// Campo de vision con el que arranca el juego
+pub const DEFAULT_FOV: f32 = std::f32::consts::FRAC_PI_3; // 60 grados
+
+// Limite de inclinacion vertical (radianes)
+pub const MAX_PITCH: f32 = 0.6;
+
+// Altura de los ojos sobre el piso (en bloques; las paredes comunes miden 1)
+pub const EYE_HEIGHT: f32 = 0.5;
+
+// Camara del raycaster: direccion de vista y plano de camara perpendicular a ella.
+// Es la unica fuente de FOV, resolucion, plano cercano e inclinacion para todos
+// los renderizadores (paredes, piso, sprites y minimapa).
+pub struct Camera {
+    pub pos: Vector2,
+    pub dir: Vector2,
+    pub plane: Vector2,
+    fov: f32,
+    pub width: u32,
+    pub height: u32,
+    pub near: f32,
+    pub pitch: f32,
+    // Altura del piso donde esta parada la camara
+    pub z: f32,
+}
+
+impl Camera {
+    // Camara con el FOV (radianes) y la resolucion de salida indicados
+    pub fn new(fov: f32, width: u32, height: u32) -> Self {
+        let mut camera = Camera {
+            pos: Vector2::zero(),
+            dir: Vector2::new(1.0, 0.0),
+            plane: Vector2::zero(),
+            fov: fov.clamp(MIN_FOV, MAX_FOV),
+            width,
+            height,
+            near: 0.05,
+            pitch: 0.0,
+            z: 0.0,
+        };
+        camera.update_plane();
+        camera
+    }
+
+    pub fn fov(&self) -> f32 { self.fov }
+
+    // Cambia el FOV en tiempo de ejecucion; afecta a todo lo que usa la camara
+    pub fn set_fov(&mut self, fov: f32) {
+        self.fov = fov.clamp(MIN_FOV, MAX_FOV);
+        self.update_plane();
+    }
+
+    pub fn set_pitch(&mut self, pitch: f32) {
+        self.pitch = pitch.clamp(-MAX_PITCH, MAX_PITCH);
+    }
+
+    pub fn set_resolution(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+    }
+
+    // Coloca la camara en la posicion y angulo del jugador
+    pub fn follow(&mut self, player: &Player) {
+        self.pos = player.pos;
+        self.dir = Vector2::new(player.a.cos(), player.a.sin());
+        self.update_plane();
+    }
+
+    // El largo del plano define el campo de vision horizontal
+    fn update_plane(&mut self) {
+        let half = (self.fov * 0.5).tan();
+        self.plane = Vector2::new(-self.dir.y * half, self.dir.x * half);
+    }
+
+    // Distancia al plano de proyeccion en pixeles
+    pub fn projection_distance(&self) -> f32 {
+        (self.width as f32 * 0.5) / (self.fov * 0.5).tan()
+    }
+
+    // Altura de los ojos en el mundo
+    pub fn eye(&self) -> f32 {
+        self.z + EYE_HEIGHT
+    }
+
+    // Fila de la pantalla donde queda el horizonte segun la inclinacion
+    pub fn horizon(&self) -> f32 {
+        self.height as f32 * 0.5 + self.projection_distance() * self.pitch.tan()
+    }
+
+    // Direccion (sin normalizar) del rayo en camera_x, de -1 (izquierda) a 1 (derecha)
+    pub fn ray_through(&self, camera_x: f32) -> Vector2 {
+        self.dir + self.plane * camera_x
+    }
+
+    // Direccion del rayo que pasa por el centro de la columna x
+    pub fn ray_dir(&self, x: u32) -> Vector2 {
+        let camera_x = 2.0 * (x as f32 + 0.5) / self.width as f32 - 1.0;
+        self.ray_through(camera_x)
+    }
+
+    // Distancia perpendicular al plano de camara de un punto del mundo
+    pub fn depth(&self, p: Vector2) -> f32 {
+        (p - self.pos).dot(self.dir)
+    }
+
+    // Lleva un punto del mundo a espacio de camara:
+    // x = desplazamiento lateral (en unidades del plano), y = profundidad
+    pub fn to_camera_space(&self, p: Vector2) -> Vector2 {
+        let rel = p - self.pos;
+        Vector2::new(
+            rel.dot(self.plane) / self.plane.length_sqr(),
+            rel.dot(self.dir),
+        )
+    }
+}
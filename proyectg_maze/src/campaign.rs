@@ -0,0 +1,251 @@
+// src/campaign.rs
+// Campaña: lista ordenada de niveles, escritos a mano (.toml / .txt) o generados con
+// una semilla fija, leida de un manifiesto TOML. El progreso del jugador (niveles
+// completados y mejores marcas) se guarda en otro archivo TOML para continuar despues.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::level::{Level, LevelError, level_from_maze, load_level};
+use crate::maze::generate::{Algorithm, GenOptions, generate_with};
+use crate::stats::LevelStats;
+
+// Version del formato del manifiesto y del archivo de progreso
+pub const CAMPAIGN_FORMAT_VERSION: u32 = 1;
+
+// De donde sale el laberinto de un nivel de la campaña
+#[derive(Debug, Clone, PartialEq)]
+pub enum LevelSource {
+    // Ruta ya resuelta respecto a la carpeta del manifiesto
+    File(String),
+    Generated { algorithm: Algorithm, width: usize, height: usize, seed: u64, options: GenOptions },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CampaignEntry {
+    // Nombre unico dentro de la campaña; es la clave del progreso guardado
+    pub name: String,
+    pub source: LevelSource,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Campaign {
+    pub name: String,
+    pub entries: Vec<CampaignEntry>,
+}
+
+// Estructura del manifiesto tal como se escribe
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CampaignFile {
+    version: u32,
+    name: Option<String>,
+    levels: Vec<EntryDef>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EntryDef {
+    name: Option<String>,
+    file: Option<String>,
+    generate: Option<GenerateDef>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GenerateDef {
+    algorithm: String,
+    width: usize,
+    height: usize,
+    seed: u64,
+    #[serde(default)]
+    braid: f32,
+    #[serde(default)]
+    rooms: usize,
+    #[serde(default)]
+    plazas: usize,
+    #[serde(default)]
+    corridors: usize,
+}
+
+pub fn load_campaign(path: &str) -> Result<Campaign, LevelError> {
+    let text = fs::read_to_string(path).map_err(|source| LevelError::Io {
+        file: path.to_string(),
+        source,
+    })?;
+    parse_campaign(&text, path)
+}
+
+// Las rutas de los niveles se resuelven respecto a la carpeta de `file`
+pub fn parse_campaign(text: &str, file: &str) -> Result<Campaign, LevelError> {
+    let invalid = |message: String| LevelError::Invalid { file: file.to_string(), message };
+
+    let def: CampaignFile = toml::from_str(text).map_err(|source| LevelError::Syntax {
+        file: file.to_string(),
+        source,
+    })?;
+    if def.version != CAMPAIGN_FORMAT_VERSION {
+        return Err(LevelError::UnsupportedVersion { file: file.to_string(), version: def.version });
+    }
+    if def.levels.is_empty() {
+        return Err(invalid("la campaña no tiene niveles".to_string()));
+    }
+
+    let dir = Path::new(file).parent().unwrap_or(Path::new(""));
+    let mut entries: Vec<CampaignEntry> = Vec::with_capacity(def.levels.len());
+    for (n, e) in def.levels.into_iter().enumerate() {
+        let (source, default_name) = match (e.file, e.generate) {
+            (Some(path), None) => {
+                let name = Path::new(&path)
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.clone());
+                (LevelSource::File(dir.join(&path).to_string_lossy().to_string()), name)
+            }
+            (None, Some(g)) => {
+                let algorithm = Algorithm::from_name(&g.algorithm).ok_or_else(|| {
+                    invalid(format!("nivel {}: algoritmo desconocido '{}'", n + 1, g.algorithm))
+                })?;
+                if g.width == 0 || g.height == 0 {
+                    return Err(invalid(format!("nivel {}: tamaño {}x{} invalido", n + 1, g.width, g.height)));
+                }
+                let options = GenOptions { braid: g.braid, rooms: g.rooms, plazas: g.plazas, corridors: g.corridors };
+                let name = format!("{} {}x{} #{}", algorithm.name(), g.width, g.height, g.seed);
+                let source = LevelSource::Generated {
+                    algorithm,
+                    width: g.width,
+                    height: g.height,
+                    seed: g.seed,
+                    options,
+                };
+                (source, name)
+            }
+            _ => return Err(invalid(format!("nivel {}: hay que indicar `file` o `generate` (solo uno)", n + 1))),
+        };
+        let name = e.name.unwrap_or(default_name);
+        if entries.iter().any(|other| other.name == name) {
+            return Err(invalid(format!("nivel {}: el nombre '{}' esta repetido", n + 1, name)));
+        }
+        entries.push(CampaignEntry { name, source });
+    }
+
+    Ok(Campaign { name: def.name.unwrap_or_else(|| "Campaña".to_string()), entries })
+}
+
+impl Campaign {
+    // Campaña de un solo nivel, para jugar un archivo suelto con --level
+    pub fn single(path: &str) -> Result<Campaign, LevelError> {
+        let level = load_level(path)?;
+        Ok(Campaign {
+            name: level.name.clone(),
+            entries: vec![CampaignEntry { name: level.name, source: LevelSource::File(path.to_string()) }],
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // Carga (o genera) el nivel `index`; el nombre del nivel es el de la campaña
+    pub fn load(&self, index: usize) -> Result<Level, LevelError> {
+        let entry = &self.entries[index];
+        let mut level = match &entry.source {
+            LevelSource::File(path) => load_level(path)?,
+            LevelSource::Generated { algorithm, width, height, seed, options } => {
+                generated_level(*algorithm, *width, *height, *seed, options, &entry.name)
+            }
+        };
+        level.name = entry.name.clone();
+        Ok(level)
+    }
+}
+
+// Nivel a partir de un laberinto generado: spawn en la primera celda libre y sin
+// sprites (los por defecto se ubican pensando en maze.txt y podrian caer en una pared)
+pub fn generated_level(
+    algorithm: Algorithm,
+    width: usize,
+    height: usize,
+    seed: u64,
+    options: &GenOptions,
+    name: &str,
+) -> Level {
+    let maze = generate_with(width, height, algorithm, seed, options);
+    let mut level = level_from_maze(maze, name);
+    level.sprites.clear();
+    level
+}
+
+// Mejor marca de un nivel completado
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LevelRecord {
+    pub best_time: f32,
+    pub best_steps: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Progress {
+    pub version: u32,
+    // Niveles completados por nombre
+    #[serde(default)]
+    pub completed: BTreeMap<String, LevelRecord>,
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Progress { version: CAMPAIGN_FORMAT_VERSION, completed: BTreeMap::new() }
+    }
+}
+
+impl Progress {
+    // Lee el progreso guardado; si el archivo no existe se empieza de cero
+    pub fn load(path: &str) -> Result<Progress, LevelError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Progress::default()),
+            Err(source) => return Err(LevelError::Io { file: path.to_string(), source }),
+        };
+        let progress: Progress = toml::from_str(&text).map_err(|source| LevelError::Syntax {
+            file: path.to_string(),
+            source,
+        })?;
+        if progress.version != CAMPAIGN_FORMAT_VERSION {
+            return Err(LevelError::UnsupportedVersion { file: path.to_string(), version: progress.version });
+        }
+        Ok(progress)
+    }
+
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        let text = toml::to_string(self).map_err(io::Error::other)?;
+        fs::write(path, text)
+    }
+
+    pub fn is_completed(&self, name: &str) -> bool {
+        self.completed.contains_key(name)
+    }
+
+    // Marca el nivel como completado, quedandose con el mejor tiempo y los menos pasos
+    pub fn record(&mut self, name: &str, stats: &LevelStats) {
+        let record = self.completed.entry(name.to_string()).or_insert(LevelRecord {
+            best_time: stats.elapsed,
+            best_steps: stats.steps,
+        });
+        record.best_time = record.best_time.min(stats.elapsed);
+        record.best_steps = record.best_steps.min(stats.steps);
+    }
+
+    // Niveles desbloqueados: los completados en orden mas el siguiente
+    pub fn unlocked(&self, campaign: &Campaign) -> usize {
+        let done = campaign.entries.iter().take_while(|e| self.is_completed(&e.name)).count();
+        (done + 1).min(campaign.len())
+    }
+}
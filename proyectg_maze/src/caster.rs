@@ -1,88 +1,294 @@
 // src/caster.rs
 
-use raylib::color::Color;
+use raylib::prelude::Vector2;
 
-use crate::framebuffer::Framebuffer;
-use crate::player::Player;
+use crate::doors::{Doors, is_door};
 use crate::maze::{is_wall, Maze};
+use crate::terrain::{Terrain, WALL_HEIGHT};
+use crate::walls::{PILLAR_HALF, WallShape, is_see_through, is_thin, wall_shape};
 
+// Cara de la celda que recibio el impacto (norte = -y en pantalla)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Face {
+    North,
+    South,
+    East,
+    West,
+}
+
+// Resultado de un rayo; distancias y posiciones en unidades de celda
 pub struct Intersect {
     pub distance: f32,
     pub impact: char,
     pub hit_x: f32,
     pub hit_y: f32,
-    pub vertical: bool
+    pub vertical: bool,
+    pub face: Face,
+    pub cell_x: usize,
+    pub cell_y: usize,
+    // Coordenada horizontal de la textura en [0..1]
+    pub tex_u: f32,
+    // Altura de lo golpeado (pared o piso elevado) y distancia a la que el rayo sale
+    // de su celda; entre `distance` y `exit` se ve la cara de arriba
+    pub height: f32,
+    pub exit: f32,
 }
 
-pub fn cast_ray(
-    framebuffer: &mut Framebuffer, 
-    maze: &Maze, 
-    player: &Player,
-    a: f32, 
-    block_size: usize, 
-    offset_x: i32, 
-    offset_y: i32,
-    draw_line: bool
-) -> Option<Intersect> {
-    let mut d = 0.0;
-    framebuffer.set_current_color(Color::PINK);
+// Devuelve el simbolo de la celda o None si esta fuera del laberinto
+fn cell_at(maze: &Maze, x: isize, y: isize) -> Option<char> {
+    if x < 0 || y < 0 { return None; }
+    let (ux, uy) = (x as usize, y as usize);
+    if uy >= maze.len() || ux >= maze[uy].len() { return None; }
+    Some(maze[uy][ux])
+}
+
+// Lanza un rayo desde `origin` (en celdas) con angulo `a` y devuelve el primer muro.
+// Las paredes finas (walls.rs) se golpean en su plano o en su pilar dentro de la
+// celda; las puertas solo en la parte de la hoja que sigue cerrada: por el hueco
+// abierto el rayo pasa de largo.
+pub fn cast_ray(maze: &Maze, doors: &Doors, origin: Vector2, a: f32) -> Option<Intersect> {
+    cast_ray_dir(maze, doors, origin, Vector2::new(a.cos(), a.sin()))
+}
+
+// Igual que cast_ray pero con un vector de direccion (no necesita estar normalizado)
+pub fn cast_ray_dir(maze: &Maze, doors: &Doors, origin: Vector2, dir: Vector2) -> Option<Intersect> {
+    walk(maze, doors, &Terrain::FLAT, origin, dir, 1).pop()
+}
+
+// Todas las paredes que cruza el rayo hasta la primera opaca que tapa todo lo de
+// atras (incluida), de la mas cercana a la mas lejana. Las transparentes
+// (walls::is_see_through) no lo detienen; de un grupo de celdas transparentes iguales
+// solo cuenta la primera cara. Con alturas (terrain.rs) tampoco lo detienen las
+// paredes bajas, y los pisos elevados cuentan como impacto. Si el rayo sale del mapa
+// la lista termina sin pared opaca.
+pub fn cast_ray_layers(maze: &Maze, doors: &Doors, terrain: &Terrain, origin: Vector2, dir: Vector2) -> Vec<Intersect> {
+    walk(maze, doors, terrain, origin, dir, MAX_LAYERS)
+}
+
+// Impactos como maximo por rayo en cast_ray_layers
+pub const MAX_LAYERS: usize = 32;
+
+// Recorrido DDA que junta hasta `max_hits` impactos
+fn walk(
+    maze: &Maze,
+    doors: &Doors,
+    terrain: &Terrain,
+    origin: Vector2,
+    dir: Vector2,
+    max_hits: usize,
+) -> Vec<Intersect> {
+    // El recorrido DDA trabaja en coordenadas de celda
+    let dir = dir.normalized();
+    let (dir_x, dir_y) = (dir.x, dir.y);
+    let mut map_x = origin.x.floor() as isize;
+    let mut map_y = origin.y.floor() as isize;
+
+    // Distancia que recorre el rayo para cruzar una celda completa en cada eje
+    let delta_x = if dir_x == 0.0 { f32::INFINITY } else { (1.0 / dir_x).abs() };
+    let delta_y = if dir_y == 0.0 { f32::INFINITY } else { (1.0 / dir_y).abs() };
+
+    // Direccion del paso y distancia hasta la primera linea de la cuadricula
+    let (step_x, mut side_x) = if dir_x < 0.0 {
+        (-1, (origin.x - map_x as f32) * delta_x)
+    } else {
+        (1, (map_x as f32 + 1.0 - origin.x) * delta_x)
+    };
+    let (step_y, mut side_y) = if dir_y < 0.0 {
+        (-1, (origin.y - map_y as f32) * delta_y)
+    } else {
+        (1, (map_y as f32 + 1.0 - origin.y) * delta_y)
+    };
 
-    let b = block_size as f32;
+    // Impacto contra la cara de entrada de un bloque; la cara golpeada es la opuesta
+    // al sentido de avance del rayo
+    let block_hit = |impact: char, (t, exit): (f32, f32), vertical: bool, map_x: isize, map_y: isize| {
+        let face = if vertical {
+            if step_x > 0 { Face::West } else { Face::East }
+        } else if step_y > 0 {
+            Face::North
+        } else {
+            Face::South
+        };
 
-    let start_x = offset_x as f32 + player.pos.x * b;
-    let start_y = offset_y as f32 + player.pos.y * b;
+        let (hit_x, hit_y) = (origin.x + t * dir_x, origin.y + t * dir_y);
+        // u en [0..1] segun la cara (coordenada local dentro de la celda)
+        let along = if vertical { hit_y - map_y as f32 } else { hit_x - map_x as f32 };
 
+        Intersect {
+            distance: t,
+            impact,
+            hit_x,
+            hit_y,
+            vertical,
+            face,
+            cell_x: map_x as usize,
+            cell_y: map_y as usize,
+            tex_u: face_u(along, face),
+            height: WALL_HEIGHT,
+            exit,
+        }
+    };
+
+    // Si el origen ya esta dentro de un muro, el impacto es inmediato
+    let mut t = 0.0;
+    let mut vertical = dir_x.abs() > dir_y.abs();
+    let mut hits = Vec::new();
+    let mut prev = None;
+
+    // Visita cada celda atravesada exactamente una vez
     loop {
-        // posición flotante del rayo
-        let xf = start_x + d * a.cos();
-        let yf = start_y + d * a.sin();
-
-        // enteros para indexar
-        let x = xf as i32;
-        let y = yf as i32;
-        
-        if x < 0 || y < 0 { return None; }
-
-        let cell_x = ((x - offset_x) as f32 / b).floor() as isize;
-        let cell_y = ((y - offset_y) as f32 / b).floor() as isize;
-
-        // límites del laberinto
-        if cell_y < 0 || cell_x < 0 { return None; }
-        let uy = cell_y as usize;
-        let ux = cell_x as usize;
-        if uy >= maze.len() || ux >= maze[uy].len() { return None; }
-
-        // gestion de colisión con muros en vertical y horizontal
-        if is_wall(maze[uy][ux]) {
-            let d_prev = if d > 0.5 { d - 0.5 } else { d };
-            let x_prev = (start_x + d_prev * a.cos()) as i32;
-            let y_prev = (start_y + d_prev * a.sin()) as i32;
-
-            let cell_x_prev = ((x_prev - offset_x) as f32 / b).floor() as isize;
-            let cell_y_prev = ((y_prev - offset_y) as f32 / b).floor() as isize;
-
-            let vertical = if ux as isize != cell_x_prev && uy as isize == cell_y_prev {
-                true  
-            } else if uy as isize != cell_y_prev && ux as isize == cell_x_prev {
-                false
-            } else {
-                a.cos().abs() > a.sin().abs()
-            };
-
-            return Some(Intersect { 
-                distance: d, 
-                impact: maze[uy][ux],
-                hit_x: xf,
-                hit_y: yf,
-                vertical,
-            });
+        let cell = cell_at(maze, map_x, map_y);
+        let here = (map_x as usize, map_y as usize);
+        let exit = side_x.min(side_y);
+        let hit = match cell {
+            None => return hits,
+            Some(c) if is_thin(c) => thin_hit(maze, doors, (here, c), origin, dir, (t, exit)),
+            Some(c) if is_wall(c) && prev != Some(c) => Some(block_hit(c, (t, exit), vertical, map_x, map_y)),
+            // piso elevado: se ve como un bloque bajo que se puede pisar
+            Some(c) if !is_wall(c) && terrain.floor_height(here) > 0.0 => {
+                Some(block_hit(c, (t, exit), vertical, map_x, map_y))
+            }
+            Some(_) => None,
+        };
+        if let Some(mut hit) = hit {
+            hit.height = terrain.height(here, hit.impact);
+            let stops = !is_see_through(hit.impact) && terrain.hides_behind(hit.height);
+            hits.push(hit);
+            if stops || hits.len() >= max_hits { return hits; }
         }
+        prev = cell.filter(|&c| is_see_through(c));
 
-        // dibuja si draw_line es verdadero
-        if draw_line {
-            framebuffer.set_pixel(x as u32, y as u32);
+        if side_x < side_y {
+            t = side_x;
+            side_x += delta_x;
+            map_x += step_x;
+            vertical = true;
+        } else {
+            t = side_y;
+            side_y += delta_y;
+            map_y += step_y;
+            vertical = false;
         }
+    }
+}
+
+// Tolerancia al comparar distancias calculadas por caminos distintos
+const EPS: f32 = 1e-4;
 
-        d += 1.0;
+// Voltea u en las caras este y norte para mantener la orientacion de la textura
+fn face_u(along: f32, face: Face) -> f32 {
+    let u = if matches!(face, Face::East | Face::North) { 1.0 - along } else { along };
+    u.clamp(0.0, 1.0)
+}
+
+// Cara golpeada de un plano que corre a lo largo de x (o de y) segun el sentido del rayo
+fn plane_face(along_x: bool, d: f32) -> Face {
+    match (along_x, d > 0.0) {
+        (true, true) => Face::North,
+        (true, false) => Face::South,
+        (false, true) => Face::West,
+        (false, false) => Face::East,
     }
-}
\ No newline at end of file
+}
+
+// Impacto contra una pared fina de `cell`, si el rayo la cruza entre las distancias
+// `enter` y `exit` (entrada y salida de la celda). Las puertas son paneles cuya hoja
+// se corre hacia el lado bajo de la celda a medida que se abren: queda cerrado el
+// tramo [open, 1] de la coordenada a lo largo de la puerta.
+fn thin_hit(
+    maze: &Maze,
+    doors: &Doors,
+    (cell, c): ((usize, usize), char),
+    origin: Vector2,
+    dir: Vector2,
+    (enter, exit): (f32, f32),
+) -> Option<Intersect> {
+    let (cx, cy) = (cell.0 as f32, cell.1 as f32);
+
+    // plano: eje a lo largo del que corre y desplazamiento dentro de la celda
+    let (along_x, offset) = match wall_shape(maze, cell, c) {
+        WallShape::Block => return None,
+        WallShape::Pillar => return pillar_hit(cell, c, origin, dir, (enter, exit)),
+        WallShape::Panel { along_x } => (along_x, 0.5),
+        WallShape::Edge(Face::North) => (true, 0.0),
+        WallShape::Edge(Face::South) => (true, 1.0),
+        WallShape::Edge(Face::West) => (false, 0.0),
+        WallShape::Edge(Face::East) => (false, 1.0),
+    };
+    let open = if is_door(c) { doors.open_fraction(cell) } else { 0.0 };
+    if open >= 1.0 { return None; }
+
+    // a lo largo de x el plano es la linea y = cy + offset; si no, x = cx + offset
+    let (d, o, plane) = if along_x { (dir.y, origin.y, cy + offset) } else { (dir.x, origin.x, cx + offset) };
+    if d == 0.0 { return None; }
+    let t = (plane - o) / d;
+    // las paredes de borde coinciden con la entrada o la salida de la celda
+    if t < enter - EPS || t > exit + EPS { return None; }
+
+    let (hit_x, hit_y) = (origin.x + t * dir.x, origin.y + t * dir.y);
+    let along = if along_x { hit_x - cx } else { hit_y - cy };
+    if along < open || along > 1.0 { return None; }
+
+    let face = plane_face(along_x, d);
+    // la hoja de una puerta se corre con la textura pegada
+    let tex_u = if is_door(c) { along - open } else { face_u(along, face) };
+    Some(Intersect {
+        distance: t,
+        impact: c,
+        hit_x,
+        hit_y,
+        vertical: !along_x,
+        face,
+        cell_x: cell.0,
+        cell_y: cell.1,
+        tex_u,
+        height: WALL_HEIGHT,
+        exit: t,
+    })
+}
+
+// Impacto contra el pilar centrado de `cell` (interseccion de rayo y caja por ejes)
+fn pillar_hit(
+    cell: (usize, usize),
+    c: char,
+    origin: Vector2,
+    dir: Vector2,
+    (enter, exit): (f32, f32),
+) -> Option<Intersect> {
+    let (lo_x, lo_y) = (cell.0 as f32 + 0.5 - PILLAR_HALF, cell.1 as f32 + 0.5 - PILLAR_HALF);
+    let side = 2.0 * PILLAR_HALF;
+
+    // distancias de entrada y salida de la franja de cada eje. Un rayo paralelo al
+    // eje esta siempre adentro de la franja (rozar el borde cuenta) o nunca
+    let slab = |o: f32, d: f32, lo: f32| {
+        if d == 0.0 {
+            let inside = (lo..=lo + side).contains(&o);
+            return if inside { (f32::NEG_INFINITY, f32::INFINITY) } else { (f32::INFINITY, f32::NEG_INFINITY) };
+        }
+        let (t1, t2) = ((lo - o) / d, (lo + side - o) / d);
+        (t1.min(t2), t1.max(t2))
+    };
+    let (near_x, far_x) = slab(origin.x, dir.x, lo_x);
+    let (near_y, far_y) = slab(origin.y, dir.y, lo_y);
+    let (near, far) = (near_x.max(near_y), far_x.min(far_y));
+    if near > far || near < enter || near > exit { return None; }
+
+    let (hit_x, hit_y) = (origin.x + near * dir.x, origin.y + near * dir.y);
+    // la ultima franja en la que entra el rayo es la de la cara golpeada
+    let vertical = near_x > near_y;
+    let face = if vertical { plane_face(false, dir.x) } else { plane_face(true, dir.y) };
+    let along = if vertical { (hit_y - lo_y) / side } else { (hit_x - lo_x) / side };
+    Some(Intersect {
+        distance: near,
+        impact: c,
+        hit_x,
+        hit_y,
+        vertical,
+        face,
+        cell_x: cell.0,
+        cell_y: cell.1,
+        tex_u: face_u(along, face),
+        height: WALL_HEIGHT,
+        exit: near,
+    })
+}
@@ -4,8 +4,12 @@ use std::f32::consts::{FRAC_PI_2, PI};
 use raylib::prelude::*;
 use raylib::consts::KeyboardKey;
 
-use crate::maze::{Maze, is_wall};
+use crate::doors::{Doors, is_door};
+use crate::maze::{Maze, is_goal, is_wall};
 use crate::player::Player;
+use crate::terrain::Terrain;
+use crate::walls::edge_between;
+use crate::camera::Camera;
 
 fn wrap_angle(mut a: f32) -> f32 { 
     let two_pi: f32 = 2.0_f32 * PI;
@@ -14,15 +18,37 @@ fn wrap_angle(mut a: f32) -> f32 {
     a
 }
 
-pub fn cell_is_free(maze: &Maze, x: isize, y: isize) -> bool {
+// Una puerta se puede atravesar cuando esta abierta del todo; con Doors::NONE
+// todas las puertas cuentan como abiertas
+pub fn cell_is_free(maze: &Maze, doors: &Doors, x: isize, y: isize) -> bool {
     if x < 0 || y < 0 { return false; }
     let (ux, uy) = (x as usize, y as usize);
     if uy >= maze.len() { return false; }
     if ux >= maze[uy].len() { return false; }
-    !is_wall(maze[uy][ux])
+    let c = maze[uy][ux];
+    if is_door(c) { return doors.is_passable((ux, uy)); }
+    !is_wall(c) || is_goal(c)
 }
 
-pub fn process_input(rl: &RaylibHandle, player: &mut Player, maze: &Maze, dt: f32) {
+// true si se puede pasar de la celda `from` a su vecina `to`: `to` esta libre, no
+// hay una pared de borde entre las dos y el escalon no es mas alto que MAX_STEP
+pub fn can_step(maze: &Maze, doors: &Doors, terrain: &Terrain, from: (isize, isize), to: (isize, isize)) -> bool {
+    if !cell_is_free(maze, doors, to.0, to.1) { return false; }
+    if from == to || from.0 < 0 || from.1 < 0 { return true; }
+    let cell = |(x, y): (isize, isize)| (x as usize, y as usize);
+    let center = |(x, y): (isize, isize)| Vector2::new(x as f32 + 0.5, y as f32 + 0.5);
+    !edge_between(maze, cell(from), cell(to)) && terrain.can_climb(center(from), center(to))
+}
+
+// true si la posicion esta dentro de una celda de salida
+pub fn on_goal(maze: &Maze, pos: Vector2) -> bool {
+    if pos.x < 0.0 || pos.y < 0.0 { return false; }
+    maze.get(pos.y as usize)
+        .and_then(|row| row.get(pos.x as usize))
+        .is_some_and(|&c| is_goal(c))
+}
+
+pub fn process_input(rl: &RaylibHandle, player: &mut Player, maze: &Maze, doors: &Doors, terrain: &Terrain, dt: f32) {
     // control de velocidades de movimiento y rotacion
     let move_speed = 3.0; // celdas / segundo
     let rot_speed  = 2.5; // radianes / segundo
@@ -61,20 +87,36 @@ pub fn process_input(rl: &RaylibHandle, player: &mut Player, maze: &Maze, dt: f3
         let dx = (dir_x * forward + right_x * strafe) * step;
         let dy = (dir_y * forward + right_y * strafe) * step;
 
-        // Control de colision con paredes, permite que el jugador se deslice en las paredes
+        // Control de colision con paredes, permite que el jugador se deslice en las paredes.
+        // Los escalones demasiado altos frenan igual que una pared
         let next_x = player.pos.x + dx;
         let next_y = player.pos.y + dy;
 
+        let here = (player.pos.x.floor() as isize, player.pos.y.floor() as isize);
         let cx = next_x.floor() as isize;
         let cy = player.pos.y.floor() as isize;
-        if cell_is_free(maze, cx, cy) {
+        if can_step(maze, doors, terrain, here, (cx, cy)) {
             player.pos.x = next_x;
         }
 
+        let here = (player.pos.x.floor() as isize, player.pos.y.floor() as isize);
         let cx2 = player.pos.x.floor() as isize;
         let cy2 = next_y.floor() as isize;
-        if cell_is_free(maze, cx2, cy2) {
+        if can_step(maze, doors, terrain, here, (cx2, cy2)) {
             player.pos.y = next_y;
         }
     }
-}
\ No newline at end of file
+}
+
+pub fn process_camera_input(rl: &RaylibHandle, camera: &mut Camera, dt: f32) {
+    let fov_speed   = 0.8; // radianes / segundo
+    let pitch_speed = 0.8; // radianes / segundo
+
+    // Zoom: '-' abre el campo de vision y '=' lo cierra
+    if rl.is_key_down(KeyboardKey::KEY_MINUS) { camera.set_fov(camera.fov() + fov_speed * dt); }
+    if rl.is_key_down(KeyboardKey::KEY_EQUAL) { camera.set_fov(camera.fov() - fov_speed * dt); }
+
+    // Inclinacion vertical de la vista
+    if rl.is_key_down(KeyboardKey::KEY_PAGE_UP)   { camera.set_pitch(camera.pitch + pitch_speed * dt); }
+    if rl.is_key_down(KeyboardKey::KEY_PAGE_DOWN) { camera.set_pitch(camera.pitch - pitch_speed * dt); }
+}
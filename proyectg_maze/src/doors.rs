@@ -0,0 +1,196 @@
+// src/doors.rs
+// Puertas del laberinto: 'D' es una puerta corrediza que se abre sola cuando alguien
+// se acerca y se cierra despues de un rato; 'R', 'B' e 'Y' son puertas con cerradura
+// que solo abre el jugador si tiene la llave de ese color. La hoja de la puerta esta
+// a mitad de la celda (hundida respecto a las paredes) y se corre hacia un costado.
+
+use raylib::prelude::Vector2;
+use std::collections::BTreeMap;
+
+use crate::maze::{Maze, is_wall};
+use crate::pathfinding::Cell;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyColor {
+    Red,
+    Blue,
+    Yellow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DoorKind {
+    Sliding,
+    Locked(KeyColor),
+}
+
+// Velocidad de apertura y cierre (fraccion por segundo)
+const DOOR_SPEED: f32 = 1.5;
+// Distancia (desde el centro de la celda) a la que una puerta se abre
+const OPEN_RADIUS: f32 = 1.3;
+// Segundos que queda abierta despues de que no hay nadie cerca
+const CLOSE_DELAY: f32 = 2.0;
+// Apertura desde la que se puede pasar: del todo, porque el caster dibuja la hoja
+// en [open, 1] y no se debe atravesar lo que todavia se ve cerrado
+const PASSABLE: f32 = 1.0;
+
+impl KeyColor {
+    pub const ALL: [KeyColor; 3] = [KeyColor::Red, KeyColor::Blue, KeyColor::Yellow];
+
+    // Simbolo de la puerta con esta cerradura
+    pub fn door_symbol(self) -> char {
+        match self {
+            KeyColor::Red => 'R',
+            KeyColor::Blue => 'B',
+            KeyColor::Yellow => 'Y',
+        }
+    }
+
+    // Textura del sprite de la llave ('b' ya es el bull terrier)
+    pub fn key_tex(self) -> char {
+        match self {
+            KeyColor::Red => 'r',
+            KeyColor::Blue => 'u',
+            KeyColor::Yellow => 'y',
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyColor::Red => "red",
+            KeyColor::Blue => "blue",
+            KeyColor::Yellow => "yellow",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<KeyColor> {
+        KeyColor::ALL.into_iter().find(|k| k.name() == name)
+    }
+}
+
+pub fn door_kind(c: char) -> Option<DoorKind> {
+    match c {
+        'D' => Some(DoorKind::Sliding),
+        _ => KeyColor::ALL.into_iter().find(|k| k.door_symbol() == c).map(DoorKind::Locked),
+    }
+}
+
+pub fn is_door(c: char) -> bool {
+    door_kind(c).is_some()
+}
+
+// true si la hoja de la puerta corre a lo largo del eje x (paredes a izquierda y
+// derecha); si no, corre a lo largo del eje y
+pub fn door_along_x(maze: &Maze, (x, y): Cell) -> bool {
+    let wall = |x: Option<usize>| {
+        x.and_then(|x| maze.get(y).and_then(|row| row.get(x))).is_some_and(|&c| is_wall(c))
+    };
+    wall(x.checked_sub(1)) && wall(Some(x + 1))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Door {
+    pub kind: DoorKind,
+    // 0 = cerrada, 1 = abierta del todo
+    pub open: f32,
+    // Tiempo que falta para empezar a cerrarse
+    pub timer: f32,
+    // Una puerta con cerradura queda corrediza despues de abrirla con la llave
+    pub unlocked: bool,
+}
+
+impl Door {
+    fn can_open(&self, keys: &[KeyColor]) -> bool {
+        match self.kind {
+            DoorKind::Sliding => true,
+            DoorKind::Locked(color) => self.unlocked || keys.contains(&color),
+        }
+    }
+
+    pub fn is_locked(&self) -> bool {
+        matches!(self.kind, DoorKind::Locked(_)) && !self.unlocked
+    }
+}
+
+// Estado de las puertas de un nivel. Las celdas de puerta que no estan en el mapa
+// cuentan como abiertas: asi las ven el validador y el pathfinding, que trabajan
+// solo con la grilla.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Doors {
+    doors: BTreeMap<Cell, Door>,
+}
+
+impl Doors {
+    // Sin estado: todas las puertas abiertas
+    pub const NONE: Doors = Doors { doors: BTreeMap::new() };
+
+    // Todas las puertas del laberinto, cerradas
+    pub fn from_maze(maze: &Maze) -> Self {
+        let mut doors = BTreeMap::new();
+        for (y, row) in maze.iter().enumerate() {
+            for (x, &c) in row.iter().enumerate() {
+                if let Some(kind) = door_kind(c) {
+                    doors.insert((x, y), Door { kind, open: 0.0, timer: 0.0, unlocked: false });
+                }
+            }
+        }
+        Doors { doors }
+    }
+
+    pub fn get(&self, cell: Cell) -> Option<&Door> {
+        self.doors.get(&cell)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Cell, &Door)> {
+        self.doors.iter().map(|(c, d)| (*c, d))
+    }
+
+    // true si alguna puerta de ese color sigue con la cerradura puesta
+    pub fn has_lock(&self, color: KeyColor) -> bool {
+        self.doors.values().any(|d| d.kind == DoorKind::Locked(color) && d.is_locked())
+    }
+
+    // Saca la cerradura de todas las puertas de ese color
+    pub fn unlock(&mut self, color: KeyColor) {
+        for door in self.doors.values_mut() {
+            if door.kind == DoorKind::Locked(color) {
+                door.unlocked = true;
+            }
+        }
+    }
+
+    pub fn open_fraction(&self, cell: Cell) -> f32 {
+        self.doors.get(&cell).map_or(1.0, |d| d.open)
+    }
+
+    // Se puede atravesar la celda de la puerta
+    pub fn is_passable(&self, cell: Cell) -> bool {
+        self.open_fraction(cell) >= PASSABLE
+    }
+
+    // Abre las puertas a las que se acerca el jugador (con sus llaves) o algun perro
+    // (solo las corredizas) y cierra las que quedan solas. No se cierra una puerta
+    // con alguien adentro.
+    pub fn update(&mut self, player: Vector2, keys: &[KeyColor], others: &[Vector2], dt: f32) {
+        for (&(x, y), door) in self.doors.iter_mut() {
+            let center = Vector2::new(x as f32 + 0.5, y as f32 + 0.5);
+            let near = |p: Vector2| (p - center).length() <= OPEN_RADIUS;
+            let inside = |p: Vector2| p.x.floor() as usize == x && p.y.floor() as usize == y;
+
+            let by_player = near(player) && door.can_open(keys);
+            let by_other = others.iter().any(|&p| near(p)) && door.can_open(&[]);
+            if by_player && door.is_locked() {
+                door.unlocked = true;
+            }
+            let occupied = inside(player) || others.iter().any(|&p| inside(p));
+
+            if by_player || by_other || occupied {
+                door.timer = CLOSE_DELAY;
+                door.open = (door.open + DOOR_SPEED * dt).min(1.0);
+            } else if door.timer > 0.0 {
+                door.timer -= dt;
+            } else {
+                door.open = (door.open - DOOR_SPEED * dt).max(0.0);
+            }
+        }
+    }
+}
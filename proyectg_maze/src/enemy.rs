@@ -0,0 +1,459 @@
+// src/enemy.rs
+// IA de los perros: cada sprite de perro tiene una maquina de estados (quieto,
+// paseando, persiguiendo al jugador, yendo a la comida, comiendo y volviendo a su
+// lugar). La vista usa el mismo rayo que el render y los recorridos usan el
+// pathfinding sobre el laberinto. Los perros abren las puertas corredizas al
+// acercarse (doors.rs) y esperan frente a ellas hasta que se abren.
+
+use raylib::prelude::Vector2;
+
+use crate::caster::cast_ray_layers;
+use crate::controller::cell_is_free;
+use crate::doors::Doors;
+use crate::items::Item;
+use crate::maze::Maze;
+use crate::maze::generate::Rng;
+use crate::pathfinding::{Cell, cell_of, find_path, reach_field};
+use crate::player::PLAYER_RADIUS;
+use crate::sprites::Sprite;
+use crate::terrain::Terrain;
+use crate::walls::is_see_through;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DogKind {
+    Corgi,
+    BullTerrier,
+    Mastiff,
+}
+
+// Valores de combate de un tipo de perro (configurables por nivel)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DogStats {
+    // Salud que le quita al jugador en cada golpe
+    pub damage: f32,
+    // Radio de contacto (en celdas)
+    pub radius: f32,
+}
+
+// Valores por defecto de cada tipo de perro
+pub fn default_dog_stats() -> Vec<(DogKind, DogStats)> {
+    vec![
+        (DogKind::Corgi, DogStats { damage: 10.0, radius: 0.4 }),
+        (DogKind::BullTerrier, DogStats { damage: 20.0, radius: 0.45 }),
+        (DogKind::Mastiff, DogStats { damage: 35.0, radius: 0.55 }),
+    ]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AiState {
+    // Espera un rato en el lugar
+    Idle,
+    // Camina hacia una celda al azar cerca de su lugar
+    Wander,
+    // Vio al jugador y lo sigue (hasta su ultima posicion conocida)
+    Chase,
+    // Perdio al jugador y vuelve a su lugar
+    Return,
+    // Va hacia un hueso o una galleta que vio (tiene prioridad sobre el jugador)
+    Fetch,
+    // Esta comiendo: no se mueve ni muerde
+    Eating,
+}
+
+// Radio (en pasos) de las celdas a las que pasea un perro alrededor de su lugar
+const WANDER_RADIUS: u32 = 4;
+// Segundos que sigue persiguiendo despues de perder de vista al jugador
+const LOSE_TIME: f32 = 2.0;
+// Distancia a la que se detiene frente al jugador
+const STOP_DISTANCE: f32 = 0.6;
+// Distancia a la que alcanza la comida y empieza a comer
+const EAT_DISTANCE: f32 = 0.3;
+// Segundos que intenta llegar a la comida antes de rendirse
+const FETCH_TIME: f32 = 6.0;
+// Segundos que no le hace caso a la comida despues de rendirse
+const FOOD_COOLDOWN: f32 = 5.0;
+
+impl DogKind {
+    // El tipo de perro sale de la textura del sprite
+    pub fn from_key(key: char) -> Option<DogKind> {
+        match key {
+            'c' | 'a' => Some(DogKind::Corgi),
+            'b' => Some(DogKind::BullTerrier),
+            'm' => Some(DogKind::Mastiff),
+            _ => None,
+        }
+    }
+
+    // Nombre en los archivos de nivel
+    pub fn name(self) -> &'static str {
+        match self {
+            DogKind::Corgi => "corgi",
+            DogKind::BullTerrier => "bull_terrier",
+            DogKind::Mastiff => "mastiff",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<DogKind> {
+        [DogKind::Corgi, DogKind::BullTerrier, DogKind::Mastiff]
+            .into_iter()
+            .find(|k| k.name() == name)
+    }
+
+    // Celdas por segundo (el jugador camina a 3)
+    pub fn speed(self, state: AiState) -> f32 {
+        let base = match self {
+            DogKind::Corgi => 2.0,
+            DogKind::BullTerrier => 2.4,
+            DogKind::Mastiff => 1.6,
+        };
+        if state == AiState::Chase { base } else { base * 0.5 }
+    }
+
+    // Distancia maxima a la que ve al jugador (en celdas)
+    pub fn sight(self) -> f32 {
+        match self {
+            DogKind::Corgi => 7.0,
+            DogKind::BullTerrier => 8.0,
+            DogKind::Mastiff => 5.0,
+        }
+    }
+
+    // Cuadros de animacion de cada estado
+    pub fn frames(self, state: AiState) -> Vec<char> {
+        match (self, state) {
+            (DogKind::Corgi, AiState::Chase) => vec!['a'],
+            (DogKind::Corgi, _) => vec!['c'],
+            (DogKind::BullTerrier, _) => vec!['b'],
+            (DogKind::Mastiff, _) => vec!['m'],
+        }
+    }
+}
+
+pub struct Enemy {
+    // Indice del sprite del nivel que mueve esta IA
+    pub sprite: usize,
+    pub kind: DogKind,
+    pub stats: DogStats,
+    pub state: AiState,
+    // Posicion a la que vuelve despues de una persecucion
+    pub home: Vector2,
+    // Celdas que faltan recorrer (la primera es la proxima)
+    pub path: Vec<Cell>,
+    // Ultima posicion en la que vio al jugador
+    pub last_seen: Option<Vector2>,
+    // Tiempo restante en Idle, sin ver al jugador en Chase o para llegar a la comida
+    // en Fetch
+    pub timer: f32,
+    // Tiempo que falta para volver a buscar comida despues de rendirse
+    food_cooldown: f32,
+    rng: Rng,
+}
+
+// Crea una IA por cada sprite de perro; el resto de los sprites queda quieto.
+// `stats` da los valores de cada tipo (los que falten usan los por defecto).
+pub fn spawn_enemies(sprites: &mut [Sprite], stats: &[(DogKind, DogStats)], seed: u64) -> Vec<Enemy> {
+    let defaults = default_dog_stats();
+    let mut enemies = Vec::new();
+    for (i, sprite) in sprites.iter_mut().enumerate() {
+        let Some(kind) = sprite.tex_keys.first().and_then(|&k| DogKind::from_key(k)) else { continue };
+        let (_, stats) = stats
+            .iter()
+            .chain(defaults.iter())
+            .find(|(k, _)| *k == kind)
+            .copied()
+            .expect("todos los tipos tienen valores por defecto");
+        let mut enemy = Enemy {
+            sprite: i,
+            kind,
+            stats,
+            state: AiState::Idle,
+            home: sprite.pos,
+            path: Vec::new(),
+            last_seen: None,
+            timer: 0.0,
+            food_cooldown: 0.0,
+            rng: Rng::new(seed.wrapping_add(i as u64)),
+        };
+        enemy.set_state(AiState::Idle, sprite);
+        enemies.push(enemy);
+    }
+    enemies
+}
+
+// true si no hay paredes entre `from` y `to` y estan a menos de `range` celdas
+// (a traves de rejas y ventanas se ve; las alturas no cuentan)
+pub fn can_see(maze: &Maze, doors: &Doors, from: Vector2, to: Vector2, range: f32) -> bool {
+    let dist = (to - from).length();
+    if dist > range { return false; }
+    if dist < 1e-4 { return true; }
+    let opaque = cast_ray_layers(maze, doors, &Terrain::FLAT, from, to - from).into_iter().find(|h| !is_see_through(h.impact));
+    match opaque {
+        Some(hit) => hit.distance >= dist,
+        None => true,
+    }
+}
+
+impl Enemy {
+    // Cambia de estado y de animacion
+    fn set_state(&mut self, state: AiState, sprite: &mut Sprite) {
+        self.state = state;
+        self.path.clear();
+        if state == AiState::Idle {
+            self.timer = 1.0 + self.rng.below(20) as f32 * 0.1;
+        }
+        sprite.tex_keys = self.kind.frames(state);
+        sprite.current_frame = 0;
+        sprite.timer = 0.0;
+    }
+
+    // Camino desde la celda actual del sprite (sin incluirla) hasta `target`
+    fn plan(&mut self, maze: &Maze, doors: &Doors, terrain: &Terrain, from: Vector2, target: Cell) -> bool {
+        let path = cell_of(from.x, from.y).and_then(|start| find_path(maze, doors, terrain, start, target));
+        match path {
+            Some(path) => {
+                self.path = path[1..].to_vec();
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Avanza por el camino hacia el centro de cada celda; false si ya no queda camino.
+    // Frente a una puerta cerrada espera a que se abra, salvo que tenga cerradura.
+    fn follow_path(&mut self, sprite: &mut Sprite, doors: &Doors, dt: f32) -> bool {
+        let mut budget = self.kind.speed(self.state) * dt;
+        while let Some(&(x, y)) = self.path.first() {
+            if !doors.is_passable((x, y)) {
+                return !doors.get((x, y)).is_some_and(|d| d.is_locked());
+            }
+            let target = Vector2::new(x as f32 + 0.5, y as f32 + 0.5);
+            let to = target - sprite.pos;
+            let dist = to.length();
+            if dist <= budget {
+                sprite.pos = target;
+                budget -= dist;
+                self.path.remove(0);
+            } else {
+                sprite.pos += to * (budget / dist);
+                return true;
+            }
+        }
+        false
+    }
+
+    // Celda al azar a la que se llega desde su lugar en pocos pasos
+    fn wander_target(&mut self, maze: &Maze, doors: &Doors, terrain: &Terrain) -> Option<Cell> {
+        let home = cell_of(self.home.x, self.home.y)?;
+        let field = reach_field(maze, doors, terrain, &[home]);
+        let cells: Vec<Cell> = field
+            .reached()
+            .filter(|&c| field.get(c).is_some_and(|d| d > 0 && d <= WANDER_RADIUS))
+            .collect();
+        if cells.is_empty() { return None; }
+        Some(cells[self.rng.below(cells.len())])
+    }
+
+    // Camina hacia `target` hasta quedar a `stop` celdas: directo si estan en la misma
+    // celda y, si no, por el camino mas corto (que se recalcula si el objetivo cambia
+    // de celda). false si no hay camino o lo corta una puerta con cerradura.
+    #[allow(clippy::too_many_arguments)]
+    fn move_towards(
+        &mut self,
+        sprite: &mut Sprite,
+        maze: &Maze,
+        doors: &Doors,
+        terrain: &Terrain,
+        target: Vector2,
+        stop: f32,
+        dt: f32,
+    ) -> bool {
+        if sprite.pos.x.floor() == target.x.floor() && sprite.pos.y.floor() == target.y.floor() {
+            self.path.clear();
+            let to = target - sprite.pos;
+            if to.length() <= stop { return true; }
+            let step = (self.kind.speed(self.state) * dt).min(to.length() - stop);
+            let next = sprite.pos + to.normalized() * step;
+            if cell_is_free(maze, doors, next.x.floor() as isize, next.y.floor() as isize) {
+                sprite.pos = next;
+            }
+            true
+        } else {
+            let target_cell = cell_of(target.x, target.y);
+            if self.path.last().copied() != target_cell {
+                self.path.clear();
+                let planned = target_cell.is_some_and(|cell| self.plan(maze, doors, terrain, sprite.pos, cell));
+                if !planned { return false; }
+            }
+            // sin camino pendiente tambien esta bien si ya llego a la celda del objetivo
+            self.follow_path(sprite, doors, dt) || cell_of(sprite.pos.x, sprite.pos.y) == target_cell
+        }
+    }
+
+    // Comida mas cercana que ve, dentro del alcance de la vista. Despues de rendirse
+    // no mira la comida por un rato.
+    fn food_in_sight(&self, maze: &Maze, doors: &Doors, pos: Vector2, items: &[Item]) -> Option<usize> {
+        if self.food_cooldown > 0.0 { return None; }
+        let range = self.kind.sight();
+        items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| item.kind.is_food())
+            .filter(|(_, item)| {
+                let dist = (item.pos() - pos).length();
+                dist <= range && can_see(maze, doors, pos, item.pos(), range)
+            })
+            .min_by(|a, b| {
+                let da = (a.1.pos() - pos).length();
+                let db = (b.1.pos() - pos).length();
+                da.total_cmp(&db)
+            })
+            .map(|(i, _)| i)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        &mut self,
+        sprite: &mut Sprite,
+        maze: &Maze,
+        doors: &Doors,
+        terrain: &Terrain,
+        player_pos: Vector2,
+        items: &mut Vec<Item>,
+        dt: f32,
+    ) {
+        if self.state == AiState::Eating {
+            self.timer -= dt;
+            if self.timer <= 0.0 {
+                self.set_state(AiState::Return, sprite);
+            }
+            return;
+        }
+        self.food_cooldown = (self.food_cooldown - dt).max(0.0);
+
+        // un perro hambriento deja todo por la comida
+        if let Some(i) = self.food_in_sight(maze, doors, sprite.pos, items) {
+            let food = items[i].pos();
+            if (food - sprite.pos).length() <= EAT_DISTANCE {
+                let item = items.remove(i);
+                self.set_state(AiState::Eating, sprite);
+                self.timer = item.kind.feed_time();
+                return;
+            }
+            if self.state != AiState::Fetch {
+                self.set_state(AiState::Fetch, sprite);
+                self.timer = FETCH_TIME;
+            }
+            self.timer -= dt;
+            // sin camino (paredes, rejas o una cerradura) o tardando demasiado se rinde
+            if !self.move_towards(sprite, maze, doors, terrain, food, 0.0, dt) || self.timer <= 0.0 {
+                self.food_cooldown = FOOD_COOLDOWN;
+                self.set_state(AiState::Return, sprite);
+            }
+            return;
+        }
+        if self.state == AiState::Fetch {
+            // la comida ya no esta (la comio otro perro o la junto el jugador) o la
+            // perdio de vista
+            self.set_state(AiState::Return, sprite);
+        }
+
+        let sees_player = can_see(maze, doors, sprite.pos, player_pos, self.kind.sight());
+        if sees_player {
+            self.last_seen = Some(player_pos);
+            self.timer = LOSE_TIME;
+            if self.state != AiState::Chase {
+                self.set_state(AiState::Chase, sprite);
+            }
+        }
+
+        match self.state {
+            AiState::Idle => {
+                self.timer -= dt;
+                if self.timer <= 0.0 {
+                    match self.wander_target(maze, doors, terrain) {
+                        Some(target) => {
+                            self.set_state(AiState::Wander, sprite);
+                            if !self.plan(maze, doors, terrain, sprite.pos, target) {
+                                self.set_state(AiState::Idle, sprite);
+                            }
+                        }
+                        None => self.timer = 1.0,
+                    }
+                }
+            }
+            AiState::Wander => {
+                if !self.follow_path(sprite, doors, dt) {
+                    self.set_state(AiState::Idle, sprite);
+                }
+            }
+            AiState::Chase => {
+                if !sees_player {
+                    self.timer -= dt;
+                }
+                let Some(target) = self.last_seen else {
+                    self.set_state(AiState::Return, sprite);
+                    return;
+                };
+                let close = (target - sprite.pos).length() <= STOP_DISTANCE;
+                if close && sees_player {
+                    // frente al jugador: se queda ahi mirando
+                    self.path.clear();
+                } else {
+                    self.move_towards(sprite, maze, doors, terrain, target, STOP_DISTANCE, dt);
+                }
+                if self.timer <= 0.0 {
+                    self.last_seen = None;
+                    self.set_state(AiState::Return, sprite);
+                }
+            }
+            // ya resueltos arriba
+            AiState::Fetch | AiState::Eating => {}
+            AiState::Return => {
+                if self.path.is_empty() {
+                    let home = cell_of(self.home.x, self.home.y);
+                    let planned = home.is_some_and(|cell| self.plan(maze, doors, terrain, sprite.pos, cell));
+                    if !planned || self.path.is_empty() {
+                        self.set_state(AiState::Idle, sprite);
+                        return;
+                    }
+                }
+                if !self.follow_path(sprite, doors, dt) {
+                    self.set_state(AiState::Idle, sprite);
+                }
+            }
+        }
+    }
+}
+
+// Actualiza la IA de todos los perros del nivel; la comida que alcanzan se saca de `items`
+#[allow(clippy::too_many_arguments)]
+pub fn update_enemies(
+    enemies: &mut [Enemy],
+    sprites: &mut [Sprite],
+    maze: &Maze,
+    doors: &Doors,
+    terrain: &Terrain,
+    player_pos: Vector2,
+    items: &mut Vec<Item>,
+    dt: f32,
+) {
+    for enemy in enemies {
+        if let Some(sprite) = sprites.get_mut(enemy.sprite) {
+            enemy.update(sprite, maze, doors, terrain, player_pos, items, dt);
+        }
+    }
+}
+
+// Mayor daño de los perros que tocan al jugador en `player_pos` (None si ninguno).
+// Un perro que esta comiendo no muerde.
+pub fn contact_damage(enemies: &[Enemy], sprites: &[Sprite], player_pos: Vector2) -> Option<f32> {
+    enemies
+        .iter()
+        .filter(|e| e.state != AiState::Eating)
+        .filter(|e| {
+            sprites.get(e.sprite)
+                .is_some_and(|s| (s.pos - player_pos).length() < e.stats.radius + PLAYER_RADIUS)
+        })
+        .map(|e| e.stats.damage)
+        .reduce(f32::max)
+}
@@ -1,8 +1,27 @@
 // src/framebuffer.rs
 
 use raylib::prelude::*;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::slice;
 use crate::maze::Maze;
 
+// Filtro usado al escalar el framebuffer al tamaño de la ventana
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScaleFilter {
+    Nearest,
+    Linear,
+}
+
+impl ScaleFilter {
+    fn to_raylib(self) -> TextureFilter {
+        match self {
+            ScaleFilter::Nearest => TextureFilter::TEXTURE_FILTER_POINT,
+            ScaleFilter::Linear => TextureFilter::TEXTURE_FILTER_BILINEAR,
+        }
+    }
+}
 
 pub struct Framebuffer {
     buffer: Vec<Color>,
@@ -10,6 +29,10 @@ pub struct Framebuffer {
     height: u32,
     current_color: Color,
     background_color: Color,
+    // Textura en GPU que se actualiza una vez por frame con el contenido del buffer
+    texture: Option<Texture2D>,
+    filter: ScaleFilter,
+    filter_dirty: bool,
 }
 
 impl Framebuffer {
@@ -29,6 +52,9 @@ impl Framebuffer {
             height,
             current_color: Color::YELLOW,
             background_color: background,
+            texture: None,
+            filter: ScaleFilter::Nearest,
+            filter_dirty: true,
         }
     }
 
@@ -56,6 +82,17 @@ impl Framebuffer {
         }
     }
 
+    // Mezcla todo el buffer con `color` (amount de 0 a 1), por ejemplo para el
+    // destello rojo cuando el jugador recibe daño
+    pub fn tint(&mut self, color: Color, amount: f32) {
+        let t = amount.clamp(0.0, 1.0);
+        if t == 0.0 { return; }
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t) as u8;
+        for pixel in self.buffer.iter_mut() {
+            *pixel = Color::new(mix(pixel.r, color.r), mix(pixel.g, color.g), mix(pixel.b, color.b), pixel.a);
+        }
+    }
+
     // Definir el color actual
     pub fn set_current_color(&mut self, color: Color) {
         self.current_color = color;
@@ -82,15 +119,120 @@ impl Framebuffer {
         }
     }
 
-    // Dibujar el laberinto
-    pub fn draw_maze(&self, window: &mut RaylibDrawHandle, _: &RaylibThread) {
+    // Mezcla un píxel con el que ya hay segun el alfa de `color`
+    pub fn blend_pixel_i32(&mut self, x: i32, y: i32, color: Color) {
+        if x >= 0 && y >= 0 && (x as u32) < self.width && (y as u32) < self.height {
+            let idx = (y as u32 * self.width + x as u32) as usize;
+            self.buffer[idx] = blend(self.buffer[idx], color);
+        }
+    }
+
+    // Definir el filtro de escalado al presentar en la ventana
+    pub fn set_filter(&mut self, filter: ScaleFilter) {
+        if self.filter != filter {
+            self.filter = filter;
+            self.filter_dirty = true;
+        }
+    }
+
+    pub fn filter(&self) -> ScaleFilter { self.filter }
+
+    // Sube el buffer a la textura (creandola si hace falta) y la dibuja escalada a la ventana
+    pub fn present(&mut self, window: &mut RaylibDrawHandle, thread: &RaylibThread) {
+        // La textura se recrea solo si cambio el tamaño del framebuffer
+        let stale = match &self.texture {
+            Some(t) => t.width() != self.width as i32 || t.height() != self.height as i32,
+            None => true,
+        };
+        if stale {
+            let image = Image::gen_image_color(self.width as i32, self.height as i32, self.background_color);
+            self.texture = Some(
+                window
+                    .load_texture_from_image(thread, &image)
+                    .expect("No se pudo crear la textura del framebuffer"),
+            );
+            self.filter_dirty = true;
+        }
+
+        let (width, height) = (self.width as f32, self.height as f32);
+        let filter = self.filter;
+        let filter_dirty = self.filter_dirty;
+        self.filter_dirty = false;
+
+        let bytes = color_bytes(&self.buffer);
+        let texture = self.texture.as_mut().unwrap();
+        if filter_dirty {
+            texture.set_texture_filter(thread, filter.to_raylib());
+        }
+        texture.update_texture(bytes).expect("Tamaño de textura del framebuffer invalido");
+
+        let dest = Rectangle::new(
+            0.0,
+            0.0,
+            window.get_screen_width() as f32,
+            window.get_screen_height() as f32,
+        );
+        window.draw_texture_pro(
+            &*texture,
+            Rectangle::new(0.0, 0.0, width, height),
+            dest,
+            Vector2::zero(),
+            0.0,
+            Color::WHITE,
+        );
+    }
+
+    // Copiar columnas completas guardadas una tras otra (height pixeles por columna)
+    // a partir de la columna x0
+    pub fn write_columns(&mut self, x0: u32, columns: &[Color]) {
+        let h = self.height as usize;
+        let w = self.width as usize;
+        for (k, col) in columns.chunks(h).enumerate() {
+            let x = x0 as usize + k;
+            if x >= w { break; }
+            for (y, &color) in col.iter().enumerate() {
+                self.buffer[y * w + x] = color;
+            }
+        }
+    }
+
+    // Contenido del buffer, fila por fila
+    pub fn pixels(&self) -> &[Color] { &self.buffer }
+
+    // Guardar el contenido en disco sin necesitar ventana: .ppm se escribe directo
+    // y cualquier otra extension (png, bmp, ...) se exporta con raylib
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        let is_ppm = Path::new(path)
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("ppm"));
+        if is_ppm {
+            return self.save_ppm(path);
+        }
+
+        let mut image = Image::gen_image_color(self.width as i32, self.height as i32, Color::BLANK);
         for y in 0..self.height {
-            let base = (y * self.width) as usize;
             for x in 0..self.width {
-                let color = self.buffer[base + x as usize];
-                window.draw_pixel(x as i32, y as i32, color);
+                image.draw_pixel(x as i32, y as i32, self.buffer[(y * self.width + x) as usize]);
             }
         }
+        // raylib no informa errores al exportar: se revisa que el archivo exista despues
+        let _ = std::fs::remove_file(path);
+        image.export_image(path);
+        if Path::new(path).exists() {
+            Ok(())
+        } else {
+            Err(io::Error::other(format!("raylib no pudo exportar {}", path)))
+        }
+    }
+
+    // Guardar como PPM binario (P6); el canal alfa se descarta
+    pub fn save_ppm(&self, path: &str) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        for c in &self.buffer {
+            out.write_all(&[c.r, c.g, c.b])?;
+        }
+        out.flush()
     }
 
     // Dibujar el jugador
@@ -119,13 +261,49 @@ impl Framebuffer {
     } 
 }
 
-// codigo suelto del framebuffer
+// Vista en bytes RGBA8 de un buffer de colores, lista para subir a la GPU
+fn color_bytes(buffer: &[Color]) -> &[u8] {
+    // Color es #[repr(C)] con cuatro u8, asi que el buffer ya esta en formato RGBA8
+    unsafe { slice::from_raw_parts(buffer.as_ptr() as *const u8, buffer.len() * 4) }
+}
+
+// `src` sobre `dst` segun el alfa de `src` (el resultado conserva el alfa de `dst`)
+pub fn blend(dst: Color, src: Color) -> Color {
+    match src.a {
+        0 => dst,
+        255 => Color { a: dst.a, ..src },
+        a => {
+            let t = a as f32 / 255.0;
+            let mix = |d: u8, s: u8| (d as f32 + (s as f32 - d as f32) * t).round() as u8;
+            Color::new(mix(dst.r, src.r), mix(dst.g, src.g), mix(dst.b, src.b), dst.a)
+        }
+    }
+}
+
 // Determina el color a partir del símbolo
 pub fn symbol_to_color(c: char) -> Color {
     match c {
         '+' | '-' | '|' | 'g' => Color::GREEN,
         ' ' => Color::DARKBROWN,
         'h' => Color::ORANGERED,
+        'o' => Color::BEIGE,
+        't' => Color::BROWN,
+        // puertas: corrediza y con cerradura de cada color
+        'D' => Color::GOLD,
+        'R' => Color::RED,
+        'B' => Color::BLUE,
+        'Y' => Color::YELLOW,
+        // paredes finas: de borde, reja, ventana y pilar
+        '_' | '~' | '[' | ']' => Color::GREEN,
+        'F' => Color::DARKGRAY,
+        'W' => Color::SKYBLUE,
+        'I' => Color::GRAY,
+        '#' => Color::DARKGRAY,
+        'H' => Color::DARKGREEN,
+        // llaves
+        'r' => Color::MAROON,
+        'u' => Color::DARKBLUE,
+        'y' => Color::ORANGE,
         _ => Color::BLACK,
     }
 }
@@ -158,3 +336,28 @@ pub fn calc_block_size_offset(
 
     (block, offset_x, offset_y)
 }
+
+// Mapeo del mundo (en celdas) a la pantalla para la vista 2D del laberinto
+#[derive(Debug, Clone, Copy)]
+pub struct MapLayout {
+    pub block_size: u32,
+    pub offset_x: i32,
+    pub offset_y: i32,
+}
+
+impl MapLayout {
+    // Ajusta el laberinto completo dentro de una ventana de win_w x win_h
+    pub fn new(maze: &Maze, win_w: u32, win_h: u32) -> Self {
+        let (block_size, offset_x, offset_y) = calc_block_size_offset(maze, win_w, win_h);
+        Self { block_size, offset_x, offset_y }
+    }
+
+    // Convierte una posicion del mundo a pixeles de pantalla
+    pub fn to_screen(self, pos: Vector2) -> (i32, i32) {
+        let b = self.block_size as f32;
+        (
+            (self.offset_x as f32 + pos.x * b) as i32,
+            (self.offset_y as f32 + pos.y * b) as i32,
+        )
+    }
+}
@@ -0,0 +1,45 @@
+// src/headless.rs
+use raylib::prelude::*;
+
+use crate::camera::Camera;
+use crate::doors::Doors;
+use crate::framebuffer::Framebuffer;
+use crate::maze::Maze;
+use crate::render3d::render3d;
+use crate::sprites::{Sprite, draw_sprites};
+use crate::terrain::Terrain;
+use crate::textures::TextureManager;
+
+// Renderiza un cuadro completo (paredes, piso, cielo y sprites) en memoria, sin
+// ventana ni GPU. El tamaño de la imagen es la resolucion de la camara.
+pub fn render_frame<'a>(
+    maze: &Maze,
+    doors: &Doors,
+    terrain: &Terrain,
+    camera: &Camera,
+    sprites: impl IntoIterator<Item = &'a Sprite>,
+    tex: &TextureManager,
+    threads: usize,
+) -> Framebuffer {
+    let mut framebuffer = Framebuffer::new(camera.width, camera.height);
+    framebuffer.set_background_color(Color::BLACK);
+    framebuffer.clear();
+
+    let zbuf = render3d(&mut framebuffer, maze, doors, terrain, camera, tex, threads);
+    draw_sprites(&mut framebuffer, camera, terrain, sprites, tex, &zbuf);
+
+    framebuffer
+}
+
+// Interpreta una pose "x,y,angulo" (celdas y radianes)
+pub fn parse_pose(s: &str) -> Option<(Vector2, f32)> {
+    let parts: Vec<f32> = s
+        .split(',')
+        .map(|p| p.trim().parse::<f32>())
+        .collect::<Result<_, _>>()
+        .ok()?;
+    match parts.as_slice() {
+        [x, y, a] => Some((Vector2::new(*x, *y), *a)),
+        _ => None,
+    }
+}
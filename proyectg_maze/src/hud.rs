@@ -1,8 +1,48 @@
 // src/hud.rs
 use raylib::prelude::*;
 
+use crate::framebuffer::symbol_to_color;
+use crate::items::Inventory;
+use crate::player::Health;
+
 /// Muestra los FPS en la esquina superior izquierda
 pub fn draw_fps_top_left(d: &mut RaylibDrawHandle) {
     let screen_w = d.get_screen_width();
     d.draw_fps(screen_w - 100, 10);
-}
\ No newline at end of file
+}
+
+// Barra de salud y vidas restantes en la esquina inferior izquierda
+pub fn draw_health(d: &mut RaylibDrawHandle, health: &Health) {
+    let (x, y, w, h) = (10, d.get_screen_height() - 34, 200, 20);
+    let fill = ((health.hp / health.max).clamp(0.0, 1.0) * w as f32) as i32;
+    let color = if health.hp / health.max > 0.3 { Color::LIME } else { Color::RED };
+    d.draw_rectangle(x, y, w, h, Color::new(0, 0, 0, 160));
+    d.draw_rectangle(x, y, fill, h, color);
+    d.draw_rectangle_lines(x, y, w, h, Color::WHITE);
+    d.draw_text(&format!("{:.0}", health.hp), x + 6, y + 2, 16, Color::WHITE);
+    if let Some(lives) = health.lives {
+        d.draw_text(&format!("Vidas: {}", lives), x + w + 12, y + 2, 16, Color::WHITE);
+    }
+}
+
+// Objetos del inventario sobre la barra de salud; el elegido va entre corchetes.
+// Las llaves se muestran como cuadrados de su color en la esquina inferior derecha.
+pub fn draw_inventory(d: &mut RaylibDrawHandle, inventory: &Inventory) {
+    let y = d.get_screen_height() - 34;
+    for (i, key) in inventory.keys.iter().enumerate() {
+        let x = d.get_screen_width() - 30 - i as i32 * 26;
+        d.draw_rectangle(x, y, 20, 20, symbol_to_color(key.door_symbol()));
+        d.draw_rectangle_lines(x, y, 20, 20, Color::WHITE);
+    }
+
+    if inventory.items.is_empty() { return; }
+    let y = d.get_screen_height() - 60;
+    let names: Vec<String> = inventory
+        .items
+        .iter()
+        .enumerate()
+        .map(|(i, kind)| if i == inventory.selected { format!("[{}]", kind.label()) } else { kind.label().to_string() })
+        .collect();
+    let text = format!("{}  (Q elegir, F lanzar, G soltar)", names.join(" "));
+    d.draw_text(&text, 10, y, 18, Color::WHITE);
+}
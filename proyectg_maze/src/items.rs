@@ -0,0 +1,216 @@
+// src/items.rs
+// Objetos que se juntan del piso (huesos, galletas y llaves), el inventario del
+// jugador y la comida lanzada o soltada que distrae a los perros hambrientos.
+
+use raylib::prelude::Vector2;
+
+use crate::controller::can_step;
+use crate::doors::{Doors, KeyColor};
+use crate::maze::Maze;
+use crate::sprites::Sprite;
+use crate::terrain::Terrain;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemKind {
+    Bone,
+    Treat,
+    // Abre las puertas con cerradura de su color
+    Key(KeyColor),
+}
+
+// Distancia a la que el jugador junta un objeto
+pub const PICKUP_RADIUS: f32 = 0.5;
+// Velocidad inicial de un objeto lanzado (celdas / segundo)
+pub const THROW_SPEED: f32 = 6.0;
+// Segundos antes de poder volver a juntar un objeto lanzado o soltado
+const PICKUP_DELAY: f32 = 1.0;
+// Frenado de un objeto lanzado (fraccion de velocidad que conserva por segundo)
+const FRICTION: f32 = 0.05;
+
+impl ItemKind {
+    pub const ALL: [ItemKind; 5] = [
+        ItemKind::Bone,
+        ItemKind::Treat,
+        ItemKind::Key(KeyColor::Red),
+        ItemKind::Key(KeyColor::Blue),
+        ItemKind::Key(KeyColor::Yellow),
+    ];
+
+    pub fn tex_key(self) -> char {
+        match self {
+            ItemKind::Bone => 'o',
+            ItemKind::Treat => 't',
+            ItemKind::Key(color) => color.key_tex(),
+        }
+    }
+
+    // Nombre en los archivos de nivel
+    pub fn name(self) -> &'static str {
+        match self {
+            ItemKind::Bone => "bone",
+            ItemKind::Treat => "treat",
+            ItemKind::Key(KeyColor::Red) => "red_key",
+            ItemKind::Key(KeyColor::Blue) => "blue_key",
+            ItemKind::Key(KeyColor::Yellow) => "yellow_key",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ItemKind> {
+        ItemKind::ALL.into_iter().find(|k| k.name() == name)
+    }
+
+    // Nombre para mostrar en el HUD
+    pub fn label(self) -> &'static str {
+        match self {
+            ItemKind::Bone => "Hueso",
+            ItemKind::Treat => "Galleta",
+            ItemKind::Key(KeyColor::Red) => "Llave roja",
+            ItemKind::Key(KeyColor::Blue) => "Llave azul",
+            ItemKind::Key(KeyColor::Yellow) => "Llave amarilla",
+        }
+    }
+
+    // Los perros solo se distraen con la comida
+    pub fn is_food(self) -> bool {
+        !matches!(self, ItemKind::Key(_))
+    }
+
+    // Segundos que un perro se queda comiendo
+    pub fn feed_time(self) -> f32 {
+        match self {
+            ItemKind::Bone => 8.0,
+            ItemKind::Treat => 4.0,
+            ItemKind::Key(_) => 0.0,
+        }
+    }
+}
+
+// Objeto en el mapa; la posicion es la de su sprite
+pub struct Item {
+    pub kind: ItemKind,
+    pub sprite: Sprite,
+    // Velocidad mientras vuela despues de lanzarlo (cero si esta en el piso)
+    pub vel: Vector2,
+    // Tiempo que falta para poder juntarlo
+    pub pickup_delay: f32,
+}
+
+impl Item {
+    pub fn new(kind: ItemKind, pos: Vector2) -> Self {
+        Item {
+            kind,
+            sprite: Sprite {
+                pos,
+                tex_keys: vec![kind.tex_key()],
+                current_frame: 0,
+                frame_time: 1.0,
+                timer: 0.0,
+                size: 0.4,
+            },
+            vel: Vector2::zero(),
+            pickup_delay: 0.0,
+        }
+    }
+
+    pub fn pos(&self) -> Vector2 {
+        self.sprite.pos
+    }
+}
+
+// Objetos que lleva el jugador, con uno elegido para lanzar o soltar. Las llaves
+// van aparte: no ocupan lugar y no se pueden soltar.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Inventory {
+    pub items: Vec<ItemKind>,
+    pub selected: usize,
+    pub keys: Vec<KeyColor>,
+}
+
+impl Inventory {
+    pub const CAPACITY: usize = 5;
+
+    pub fn new() -> Self {
+        Inventory::default()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.items.len() >= Inventory::CAPACITY
+    }
+
+    // Agrega un objeto; false si no hay lugar
+    pub fn add(&mut self, kind: ItemKind) -> bool {
+        if let ItemKind::Key(color) = kind {
+            if !self.keys.contains(&color) {
+                self.keys.push(color);
+            }
+            return true;
+        }
+        if self.is_full() { return false; }
+        self.items.push(kind);
+        true
+    }
+
+    pub fn selected_kind(&self) -> Option<ItemKind> {
+        self.items.get(self.selected).copied()
+    }
+
+    // Pasa al siguiente objeto del inventario
+    pub fn cycle(&mut self) {
+        if !self.items.is_empty() {
+            self.selected = (self.selected + 1) % self.items.len();
+        }
+    }
+
+    // Saca el objeto elegido
+    pub fn take_selected(&mut self) -> Option<ItemKind> {
+        if self.selected >= self.items.len() { return None; }
+        let kind = self.items.remove(self.selected);
+        if self.selected >= self.items.len() {
+            self.selected = self.items.len().saturating_sub(1);
+        }
+        Some(kind)
+    }
+}
+
+// Objeto lanzado desde `pos` en la direccion del angulo `a` (speed 0 = soltarlo ahi)
+pub fn launch_item(kind: ItemKind, pos: Vector2, a: f32, speed: f32) -> Item {
+    let mut item = Item::new(kind, pos);
+    item.vel = Vector2::new(a.cos(), a.sin()) * speed;
+    item.pickup_delay = PICKUP_DELAY;
+    item
+}
+
+// Mueve los objetos lanzados: frenan con el tiempo y se detienen contra las paredes
+// y los escalones altos
+pub fn update_items(items: &mut [Item], maze: &Maze, doors: &Doors, terrain: &Terrain, dt: f32) {
+    for item in items {
+        item.pickup_delay = (item.pickup_delay - dt).max(0.0);
+        if item.vel.length() < 0.1 {
+            item.vel = Vector2::zero();
+            continue;
+        }
+        let next = item.sprite.pos + item.vel * dt;
+        let here = (item.sprite.pos.x.floor() as isize, item.sprite.pos.y.floor() as isize);
+        if can_step(maze, doors, terrain, here, (next.x.floor() as isize, next.y.floor() as isize)) {
+            item.sprite.pos = next;
+            item.vel *= FRICTION.powf(dt);
+        } else {
+            item.vel = Vector2::zero();
+        }
+    }
+}
+
+// Pasa al inventario los objetos al alcance del jugador; devuelve cuantos junto
+pub fn pick_up_items(items: &mut Vec<Item>, player_pos: Vector2, inventory: &mut Inventory) -> usize {
+    let mut picked = 0;
+    items.retain(|item| {
+        let reachable = item.pickup_delay <= 0.0 && (item.pos() - player_pos).length() <= PICKUP_RADIUS;
+        if reachable && inventory.add(item.kind) {
+            picked += 1;
+            false
+        } else {
+            true
+        }
+    });
+    picked
+}
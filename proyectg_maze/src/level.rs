@@ -0,0 +1,390 @@
+// src/level.rs
+// Niveles: laberinto mas metadatos (nombre, tiempo par, spawn, salida, sprites y
+// texturas por simbolo). Se leen de un archivo TOML versionado con la grilla ASCII
+// embebida; los .txt de siempre se cargan como nivel con valores por defecto.
+
+use raylib::prelude::*;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::f32::consts::FRAC_PI_4;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::enemy::{DogKind, DogStats, default_dog_stats};
+use crate::doors::{DoorKind, Doors, KeyColor};
+use crate::items::{Item, ItemKind};
+use crate::maze::{Maze, MazeError, find_first_free_cell, is_wall, load_maze, parse_maze};
+use crate::pathfinding::{Cell, cell_of, unlock_reachable};
+use crate::sprites::{Sprite, default_sprites};
+use crate::terrain::Terrain;
+use crate::textures::default_texture_files;
+
+// Version del formato que entiende este codigo
+pub const LEVEL_FORMAT_VERSION: u32 = 1;
+// Salud inicial del jugador si el nivel no indica otra
+pub const DEFAULT_HEALTH: f32 = 100.0;
+
+pub struct Level {
+    pub name: String,
+    // Tiempo de referencia para terminar el nivel (segundos)
+    pub par_time: Option<f32>,
+    pub maze: Maze,
+    pub spawn: Vector2,
+    pub spawn_angle: f32,
+    // Celda de la salida (columna, fila)
+    pub goal: Option<(usize, usize)>,
+    pub sprites: Vec<Sprite>,
+    // Objetos en el piso que se pueden juntar
+    pub items: Vec<Item>,
+    // Estado de las puertas de la grilla (todas cerradas al cargar)
+    pub doors: Doors,
+    // Altura de las paredes y del piso de cada celda
+    pub terrain: Terrain,
+    // Mapeo simbolo -> imagen completo (el por defecto con los cambios del nivel)
+    pub textures: Vec<(char, String)>,
+    // Salud del jugador y vidas (None = sin vidas extra)
+    pub max_health: f32,
+    pub lives: Option<u32>,
+    // Daño y radio de cada tipo de perro (los por defecto con los cambios del nivel)
+    pub dog_stats: Vec<(DogKind, DogStats)>,
+}
+
+#[derive(Debug)]
+pub enum LevelError {
+    Io { file: String, source: io::Error },
+    // TOML mal formado o con campos de tipo incorrecto
+    Syntax { file: String, source: toml::de::Error },
+    UnsupportedVersion { file: String, version: u32 },
+    // Errores de la grilla (las lineas son relativas a `grid`)
+    Maze(MazeError),
+    // Datos que se leen bien pero no tienen sentido en la grilla
+    Invalid { file: String, message: String },
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LevelError::Io { file, source } => write!(f, "{}: no se pudo abrir el nivel: {}", file, source),
+            LevelError::Syntax { file, source } => write!(f, "{}: {}", file, source),
+            LevelError::UnsupportedVersion { file, version } => write!(
+                f, "{}: version de formato {} no soportada (se espera {})",
+                file, version, LEVEL_FORMAT_VERSION
+            ),
+            LevelError::Maze(e) => write!(f, "{}", e),
+            LevelError::Invalid { file, message } => write!(f, "{}: {}", file, message),
+        }
+    }
+}
+
+impl std::error::Error for LevelError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LevelError::Io { source, .. } => Some(source),
+            LevelError::Syntax { source, .. } => Some(source),
+            LevelError::Maze(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// Estructura del archivo TOML tal como se escribe
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LevelFile {
+    version: u32,
+    name: Option<String>,
+    par_time: Option<f32>,
+    grid: String,
+    // alturas por celda alineadas con `grid` (ver terrain.rs)
+    heights: Option<String>,
+    spawn: Option<SpawnDef>,
+    goal: Option<CellDef>,
+    #[serde(default)]
+    textures: BTreeMap<char, String>,
+    #[serde(default)]
+    entities: Vec<EntityDef>,
+    #[serde(default)]
+    items: Vec<ItemDef>,
+    player: Option<PlayerDef>,
+    #[serde(default)]
+    dogs: BTreeMap<String, DogDef>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ItemDef {
+    x: f32,
+    y: f32,
+    // "bone" o "treat"
+    kind: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PlayerDef {
+    health: Option<f32>,
+    lives: Option<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DogDef {
+    damage: Option<f32>,
+    radius: Option<f32>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SpawnDef {
+    x: f32,
+    y: f32,
+    // radianes; 0 mira hacia +x
+    #[serde(default)]
+    angle: f32,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CellDef {
+    x: usize,
+    y: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EntityDef {
+    x: f32,
+    y: f32,
+    // simbolos de textura de cada cuadro de la animacion
+    frames: Vec<char>,
+    #[serde(default = "default_frame_time")]
+    frame_time: f32,
+    #[serde(default = "default_size")]
+    size: f32,
+}
+
+fn default_frame_time() -> f32 { 0.1 }
+fn default_size() -> f32 { 1.0 }
+
+// Carga un nivel .toml; cualquier otra extension se lee como laberinto de texto
+pub fn load_level(path: &str) -> Result<Level, LevelError> {
+    let is_toml = Path::new(path).extension().is_some_and(|e| e == "toml");
+    if !is_toml {
+        let maze = load_maze(path).map_err(LevelError::Maze)?;
+        let level = level_from_maze(maze, &level_name(path));
+        // un .txt no trae objetos: una puerta con cerradura nunca se podria abrir
+        check_locks(&level.maze, &level.terrain, level.spawn, &level.items)
+            .map_err(|message| LevelError::Invalid { file: path.to_string(), message })?;
+        return Ok(level);
+    }
+
+    let text = fs::read_to_string(path).map_err(|source| LevelError::Io {
+        file: path.to_string(),
+        source,
+    })?;
+    parse_level(&text, path)
+}
+
+// Nivel a partir de una grilla sola: spawn en la primera celda libre mirando a 45
+// grados, salida en la primera 'g' y el sprite de siempre
+pub fn level_from_maze(maze: Maze, name: &str) -> Level {
+    let (spawn_x, spawn_y) = find_first_free_cell(&maze).unwrap_or((0, 0));
+    let goal = find_goal(&maze);
+    Level {
+        name: name.to_string(),
+        par_time: None,
+        spawn: Vector2::new(spawn_x as f32 + 0.5, spawn_y as f32 + 0.5),
+        spawn_angle: FRAC_PI_4,
+        goal,
+        sprites: default_sprites(spawn_x, spawn_y),
+        items: Vec::new(),
+        doors: Doors::from_maze(&maze),
+        terrain: Terrain::FLAT,
+        textures: default_texture_files(),
+        max_health: DEFAULT_HEALTH,
+        lives: None,
+        dog_stats: default_dog_stats(),
+        maze,
+    }
+}
+
+// Celdas de las llaves que hay entre los objetos
+pub fn key_cells(items: &[Item]) -> Vec<(KeyColor, Cell)> {
+    items
+        .iter()
+        .filter_map(|i| match i.kind {
+            ItemKind::Key(color) => cell_of(i.pos().x, i.pos().y).map(|cell| (color, cell)),
+            _ => None,
+        })
+        .collect()
+}
+
+// Cada cerradura necesita su llave en el nivel, y desde el spawn se tiene que poder
+// abrir todas: se juntan las llaves a mano y se abre lo que se pueda hasta que no
+// quede nada (una llave detras de su propia puerta, o dos llaves cada una detras de
+// la puerta de la otra, no sirven)
+fn check_locks(maze: &Maze, terrain: &Terrain, spawn: Vector2, items: &[Item]) -> Result<(), String> {
+    let doors = Doors::from_maze(maze);
+    for ((x, y), door) in doors.iter() {
+        if let DoorKind::Locked(color) = door.kind
+            && !items.iter().any(|i| i.kind == ItemKind::Key(color))
+        {
+            return Err(format!("la puerta {} en ({}, {}) no tiene su llave en el nivel", color.name(), x, y));
+        }
+    }
+
+    let Some(start) = cell_of(spawn.x, spawn.y) else { return Ok(()) };
+    let opened = unlock_reachable(maze, &doors, terrain, start, &key_cells(items));
+    let locked = opened.iter().find_map(|(cell, d)| match d.kind {
+        DoorKind::Locked(color) if d.is_locked() => Some((cell, color)),
+        _ => None,
+    });
+    if let Some(((x, y), color)) = locked {
+        return Err(format!(
+            "la puerta {} en ({}, {}) no se puede abrir: su llave no se alcanza desde el spawn",
+            color.name(), x, y
+        ));
+    }
+    Ok(())
+}
+
+pub fn parse_level(text: &str, file: &str) -> Result<Level, LevelError> {
+    let invalid = |message: String| LevelError::Invalid { file: file.to_string(), message };
+
+    let def: LevelFile = toml::from_str(text).map_err(|source| LevelError::Syntax {
+        file: file.to_string(),
+        source,
+    })?;
+    if def.version != LEVEL_FORMAT_VERSION {
+        return Err(LevelError::UnsupportedVersion { file: file.to_string(), version: def.version });
+    }
+
+    let mut maze = parse_maze(&def.grid, &format!("{} [grid]", file)).map_err(LevelError::Maze)?;
+
+    let (rows, cols) = (maze.len(), maze[0].len());
+    let in_grid = |x: f32, y: f32| x >= 0.0 && y >= 0.0 && (y as usize) < rows && (x as usize) < cols;
+
+    let goal = match def.goal {
+        Some(CellDef { x, y }) => {
+            if !in_grid(x as f32, y as f32) {
+                return Err(invalid(format!("la salida ({}, {}) esta fuera de la grilla", x, y)));
+            }
+            maze[y][x] = 'g';
+            Some((x, y))
+        }
+        None => find_goal(&maze),
+    };
+
+    let (spawn, spawn_angle) = match def.spawn {
+        Some(s) => {
+            if !in_grid(s.x, s.y) || is_wall(maze[s.y as usize][s.x as usize]) {
+                return Err(invalid(format!("el spawn ({}, {}) no esta en una celda libre", s.x, s.y)));
+            }
+            (Vector2::new(s.x, s.y), s.angle)
+        }
+        None => {
+            let (x, y) = find_first_free_cell(&maze)
+                .ok_or_else(|| invalid("no hay celdas libres para el spawn".to_string()))?;
+            (Vector2::new(x as f32 + 0.5, y as f32 + 0.5), FRAC_PI_4)
+        }
+    };
+
+    let mut sprites = Vec::with_capacity(def.entities.len());
+    for (n, e) in def.entities.iter().enumerate() {
+        if e.frames.is_empty() {
+            return Err(invalid(format!("la entidad {} no tiene cuadros", n + 1)));
+        }
+        if !in_grid(e.x, e.y) {
+            return Err(invalid(format!("la entidad {} ({}, {}) esta fuera de la grilla", n + 1, e.x, e.y)));
+        }
+        sprites.push(Sprite {
+            pos: Vector2::new(e.x, e.y),
+            tex_keys: e.frames.clone(),
+            current_frame: 0,
+            frame_time: e.frame_time,
+            timer: 0.0,
+            size: e.size,
+        });
+    }
+
+    let mut items = Vec::with_capacity(def.items.len());
+    for (n, it) in def.items.iter().enumerate() {
+        let kind = ItemKind::from_name(&it.kind)
+            .ok_or_else(|| invalid(format!("el objeto {} es de un tipo desconocido '{}'", n + 1, it.kind)))?;
+        if !in_grid(it.x, it.y) || is_wall(maze[it.y as usize][it.x as usize]) {
+            return Err(invalid(format!("el objeto {} ({}, {}) no esta en una celda libre", n + 1, it.x, it.y)));
+        }
+        items.push(Item::new(kind, Vector2::new(it.x, it.y)));
+    }
+
+    let terrain = match &def.heights {
+        Some(text) => Terrain::from_grid(&maze, text).map_err(|e| invalid(format!("heights: {}", e)))?,
+        None => Terrain::FLAT,
+    };
+
+    check_locks(&maze, &terrain, spawn, &items).map_err(invalid)?;
+
+    // el nivel solo cambia los simbolos que declara
+    let mut textures = default_texture_files();
+    for (ch, path) in def.textures {
+        match textures.iter_mut().find(|(c, _)| *c == ch) {
+            Some(entry) => entry.1 = path,
+            None => textures.push((ch, path)),
+        }
+    }
+
+    let (max_health, lives) = match def.player {
+        Some(p) => (p.health.unwrap_or(DEFAULT_HEALTH), p.lives),
+        None => (DEFAULT_HEALTH, None),
+    };
+    if max_health <= 0.0 {
+        return Err(invalid(format!("la salud del jugador debe ser positiva ({})", max_health)));
+    }
+    if lives == Some(0) {
+        return Err(invalid("`lives` debe ser al menos 1".to_string()));
+    }
+
+    // igual que las texturas: solo cambian los tipos de perro que se declaran
+    let mut dog_stats = default_dog_stats();
+    for (name, dog) in def.dogs {
+        let kind = DogKind::from_name(&name)
+            .ok_or_else(|| invalid(format!("tipo de perro desconocido '{}'", name)))?;
+        let entry = dog_stats.iter_mut().find(|(k, _)| *k == kind).expect("todos los tipos tienen valores");
+        if let Some(damage) = dog.damage { entry.1.damage = damage; }
+        if let Some(radius) = dog.radius { entry.1.radius = radius; }
+    }
+
+    let doors = Doors::from_maze(&maze);
+    Ok(Level {
+        name: def.name.unwrap_or_else(|| level_name(file)),
+        par_time: def.par_time,
+        maze,
+        spawn,
+        spawn_angle,
+        goal,
+        sprites,
+        items,
+        doors,
+        terrain,
+        textures,
+        max_health,
+        lives,
+        dog_stats,
+    })
+}
+
+fn find_goal(maze: &Maze) -> Option<(usize, usize)> {
+    maze.iter()
+        .enumerate()
+        .find_map(|(y, row)| row.iter().position(|&c| c == 'g').map(|x| (x, y)))
+}
+
+// Nombre por defecto: el del archivo sin extension
+fn level_name(path: &str) -> String {
+    Path::new(path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
@@ -0,0 +1,28 @@
+// src/lib.rs
+// Biblioteca del laberinto: carga de mapas, raycasting, render, sprites y entrada.
+// El juego (main.rs) y las herramientas (editores, validadores, benchmarks) se
+// construyen sobre esta misma API.
+
+pub mod maze;
+pub mod render3d;
+pub mod player;
+pub mod caster;
+pub mod controller;
+pub mod textures;
+pub mod sprites;
+pub mod framebuffer;
+pub mod minimap;
+pub mod hud;
+pub mod camera;
+pub mod resolution;
+pub mod headless;
+pub mod validate;
+pub mod level;
+pub mod pathfinding;
+pub mod stats;
+pub mod campaign;
+pub mod enemy;
+pub mod items;
+pub mod doors;
+pub mod walls;
+pub mod terrain;
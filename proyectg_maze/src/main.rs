@@ -1,37 +1,305 @@
 // src/main.rs
 
 use raylib::prelude::*;
-use std::f32::consts::FRAC_PI_4;
-
-mod maze;
-mod render3d;
-mod player;
-mod caster;
-mod controller;
-mod textures;
-mod sprites;
-mod framebuffer;
-mod minimap;
-mod hud;
-
-use maze::{load_maze, render_maze, find_first_free_cell};
-use render3d::render3d;
-use player::Player;
-use caster::cast_ray;
-use controller::process_input;
-use textures::TextureManager;
-use sprites::{Sprite, draw_sprites};
-use framebuffer::{Framebuffer, calc_block_size_offset};
-use minimap::draw_minimap;
-use hud::draw_fps_top_left;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use proyectg_maze::maze::generate::{Algorithm, GenOptions, generate_with};
+use proyectg_maze::maze::metrics::difficulty;
+use proyectg_maze::render3d::{render3d, default_threads};
+use proyectg_maze::player::{Health, INVULNERABLE_TIME, Player};
+use proyectg_maze::controller::{process_input, process_camera_input, on_goal};
+use proyectg_maze::textures::TextureManager;
+use proyectg_maze::sprites::draw_sprites;
+use proyectg_maze::framebuffer::{Framebuffer, ScaleFilter};
+use proyectg_maze::minimap::{draw_minimap, draw_minimap_path};
+use proyectg_maze::camera::{Camera, DEFAULT_FOV};
+use proyectg_maze::resolution::{ResolutionScaler, RenderResolution, parse_resolution};
+use proyectg_maze::headless::{render_frame, parse_pose};
+use proyectg_maze::validate::validate_level;
+use proyectg_maze::level::{Level, LevelError, load_level};
+use proyectg_maze::campaign::{Campaign, Progress, generated_level, load_campaign};
+use proyectg_maze::pathfinding::{PathCache, cell_of};
+use proyectg_maze::stats::{LevelStats, format_time};
+use proyectg_maze::enemy::{Enemy, contact_damage, spawn_enemies, update_enemies};
+use proyectg_maze::hud::{draw_health, draw_inventory};
+use proyectg_maze::items::{THROW_SPEED, launch_item, pick_up_items, update_items};
+
+// Campaña que se juega si no se indica un nivel suelto con --level
+const DEFAULT_CAMPAIGN: &str = "levels/campaign.toml";
+// Archivo donde se guarda el progreso de la campaña
+const PROGRESS_FILE: &str = "progress.toml";
+// Rapidez con la que la vista sigue la altura del piso al subir o bajar escalones
+const STEP_EASE: f32 = 12.0;
+
+// Devuelve el valor o termina el programa mostrando el error del archivo
+fn or_exit<T>(result: Result<T, LevelError>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+// Imprime los problemas de un nivel ya cargado; false si tiene errores
+fn report_level(label: &str, level: Result<Level, LevelError>) -> bool {
+    let level = match level {
+        Ok(level) => level,
+        Err(e) => {
+            eprintln!("{}", e);
+            return false;
+        }
+    };
+    let report = validate_level(&level);
+    for issue in &report.issues {
+        let kind = if issue.is_error() { "error" } else { "aviso" };
+        println!("{}: {}: {}", label, kind, issue);
+    }
+    if report.is_valid() {
+        println!("{}: OK", label);
+    }
+    report.is_valid()
+}
+
+// Subcomando `validate [ARCHIVO...]`: revisa cada nivel (.toml o .txt; todos los de
+// la campaña por defecto si no se indica ninguno) e imprime sus problemas. Devuelve
+// false si alguno tiene errores.
+fn run_validate(files: &[String]) -> bool {
+    let mut ok = true;
+    if files.is_empty() {
+        let campaign = match load_campaign(DEFAULT_CAMPAIGN) {
+            Ok(campaign) => campaign,
+            Err(e) => {
+                eprintln!("{}", e);
+                return false;
+            }
+        };
+        for (i, entry) in campaign.entries.iter().enumerate() {
+            let label = format!("{} [{}]", DEFAULT_CAMPAIGN, entry.name);
+            ok &= report_level(&label, campaign.load(i));
+        }
+        return ok;
+    }
+
+    for file in files {
+        ok &= report_level(file, load_level(file));
+    }
+    ok
+}
+
+// Subcomando `generate [ALGORITMO] [ANCHOxALTO] [SEMILLA] [--braid F] [--rooms N]
+// [--plazas N] [--corridors N]`: imprime un laberinto generado (en celdas; el mapa
+// mide 2*ancho+1 x 2*alto+1) listo para guardar como .txt, y su dificultad en stderr
+fn run_generate(args: &[String]) -> bool {
+    let mut positional = Vec::new();
+    let mut options = GenOptions::default();
+    let mut i = 0;
+    while i < args.len() {
+        let value = args.get(i + 1);
+        let ok = match (args[i].as_str(), value) {
+            ("--braid", Some(v)) => v.parse().map(|f| options.braid = f).is_ok(),
+            ("--rooms", Some(v)) => v.parse().map(|n| options.rooms = n).is_ok(),
+            ("--plazas", Some(v)) => v.parse().map(|n| options.plazas = n).is_ok(),
+            ("--corridors", Some(v)) => v.parse().map(|n| options.corridors = n).is_ok(),
+            (flag, _) if flag.starts_with("--") => {
+                eprintln!("Opcion desconocida o sin valor: {}", flag);
+                return false;
+            }
+            (other, _) => {
+                positional.push(other.to_string());
+                i += 1;
+                continue;
+            }
+        };
+        if !ok {
+            eprintln!("Valor invalido para {}: {}", args[i], args[i + 1]);
+            return false;
+        }
+        i += 2;
+    }
+
+    let algorithm = match positional.first() {
+        Some(name) => match Algorithm::from_name(name) {
+            Some(a) => a,
+            None => {
+                let names: Vec<_> = Algorithm::ALL.iter().map(|a| a.name()).collect();
+                eprintln!("Algoritmo desconocido: {} (opciones: {})", name, names.join(", "));
+                return false;
+            }
+        },
+        None => Algorithm::Backtracker,
+    };
+    let (w, h) = match positional.get(1).map(|s| parse_resolution(s)) {
+        Some(Some(RenderResolution::Fixed(w, h))) => (w as usize, h as usize),
+        None => (8, 6),
+        _ => {
+            eprintln!("Tamaño invalido: {}", positional[1]);
+            return false;
+        }
+    };
+    let seed = match positional.get(2).map(|s| s.parse::<u64>()) {
+        Some(Ok(seed)) => seed,
+        None => time_seed(),
+        Some(Err(_)) => {
+            eprintln!("Semilla invalida: {}", positional[2]);
+            return false;
+        }
+    };
+
+    let maze = generate_with(w, h, algorithm, seed, &options);
+    eprintln!("{} semilla {}: {}", algorithm.name(), seed, difficulty(&maze));
+    for row in maze {
+        println!("{}", row.into_iter().collect::<String>());
+    }
+    true
+}
+
+fn time_seed() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or(0)
+}
+
+// Nivel con un laberinto aleatorio para la opcion del menu de inicio
+fn random_level(seed: u64) -> Level {
+    let algorithm = Algorithm::ALL[(seed % Algorithm::ALL.len() as u64) as usize];
+    let options = GenOptions { braid: 0.3, rooms: 2, plazas: 0, corridors: 1 };
+    let name = format!("Aleatorio ({}, semilla {})", algorithm.name(), seed);
+    let level = generated_level(algorithm, 12, 9, seed, &options, &name);
+    println!("Laberinto aleatorio {} (semilla {}): {}", algorithm.name(), seed, difficulty(&level.maze));
+    level
+}
+
+// Estado que se reinicia al empezar un nivel: jugador en el spawn con la salud
+// completa, estadisticas en cero y la IA de los perros del nivel
+fn start_level(level: &mut Level) -> (Player, Health, LevelStats, Vec<Enemy>) {
+    let player = Player::new(level.spawn, level.spawn_angle);
+    let health = Health::new(level.max_health, level.lives);
+    let enemies = spawn_enemies(&mut level.sprites, &level.dog_stats, 0);
+    (player, health, LevelStats::new(), enemies)
+}
+
+// Renderiza un cuadro sin abrir ventana y lo guarda en `out` (.png, .ppm, ...)
+fn take_screenshot(level: &Level, out: &str, pose: Option<(Vector2, f32)>, size: (u32, u32), threads: usize) {
+    let texman = TextureManager::load_headless_from(&level.textures);
+
+    let (pos, a) = pose.unwrap_or((level.spawn, level.spawn_angle));
+    let player = Player::new(pos, a);
+
+    let mut camera = Camera::new(DEFAULT_FOV, size.0, size.1);
+    camera.follow(&player);
+    camera.z = level.terrain.floor_at(pos);
+
+    let sprites = level.sprites.iter().chain(level.items.iter().map(|i| &i.sprite));
+    let frame = render_frame(&level.maze, &level.doors, &level.terrain, &camera, sprites, &texman, threads);
+    match frame.save(out) {
+        Ok(()) => println!("Imagen guardada en {}", out),
+        Err(e) => {
+            eprintln!("No se pudo guardar {}: {}", out, e);
+            std::process::exit(1);
+        }
+    }
+}
 
 fn main() {
     let window_width: i32 = 1000;
     let window_height: i32 = 800;
 
+    // Resolucion interna: --res 320x200 | --res 0.5 | --res 50%, y --dynamic para
+    // bajarla automaticamente cuando el render se pasa del presupuesto del frame
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("validate") => std::process::exit(if run_validate(&args[2..]) { 0 } else { 1 }),
+        Some("generate") => std::process::exit(if run_generate(&args[2..]) { 0 } else { 1 }),
+        _ => {}
+    }
+
+    // --campaign ARCHIVO elige el manifiesto de la campaña
+    // --level ARCHIVO juega un nivel suelto (.toml, o .txt con solo la grilla)
+    // --threads N reparte las columnas del render 3D en N hilos (1 = un solo hilo)
+    // --screenshot OUT [--at X,Y,ANGULO] [--size WxH] renderiza sin ventana y termina
+    let mut campaign_path = DEFAULT_CAMPAIGN.to_string();
+    let mut level_path: Option<String> = None;
+    let mut render_res = RenderResolution::Scale(1.0);
+    let mut dynamic = false;
+    let mut threads = default_threads();
+    let mut screenshot: Option<String> = None;
+    let mut pose: Option<(Vector2, f32)> = None;
+    let mut shot_size = (window_width as u32, window_height as u32);
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--res" if i + 1 < args.len() => {
+                match parse_resolution(&args[i + 1]) {
+                    Some(r) => render_res = r,
+                    None => eprintln!("Resolucion invalida: {}", args[i + 1]),
+                }
+                i += 1;
+            }
+            "--level" if i + 1 < args.len() => {
+                level_path = Some(args[i + 1].clone());
+                i += 1;
+            }
+            "--campaign" if i + 1 < args.len() => {
+                campaign_path = args[i + 1].clone();
+                i += 1;
+            }
+            "--dynamic" => dynamic = true,
+            "--threads" if i + 1 < args.len() => {
+                match args[i + 1].parse::<usize>() {
+                    Ok(n) if n > 0 => threads = n,
+                    _ => eprintln!("Cantidad de hilos invalida: {}", args[i + 1]),
+                }
+                i += 1;
+            }
+            "--screenshot" if i + 1 < args.len() => {
+                screenshot = Some(args[i + 1].clone());
+                i += 1;
+            }
+            "--at" if i + 1 < args.len() => {
+                pose = parse_pose(&args[i + 1]);
+                if pose.is_none() { eprintln!("Pose invalida: {}", args[i + 1]); }
+                i += 1;
+            }
+            "--size" if i + 1 < args.len() => {
+                match parse_resolution(&args[i + 1]) {
+                    Some(RenderResolution::Fixed(w, h)) => shot_size = (w, h),
+                    _ => eprintln!("Tamaño invalido: {}", args[i + 1]),
+                }
+                i += 1;
+            }
+            other => eprintln!("Argumento desconocido: {}", other),
+        }
+        i += 1;
+    }
+
+    let campaign = match &level_path {
+        Some(path) => or_exit(Campaign::single(path)),
+        None => or_exit(load_campaign(&campaign_path)),
+    };
+
+    if let Some(out) = screenshot {
+        take_screenshot(&or_exit(campaign.load(0)), &out, pose, shot_size, threads);
+        return;
+    }
+
+    // Progreso guardado: si no se puede leer se avisa y se empieza de cero
+    let mut progress = Progress::load(PROGRESS_FILE).unwrap_or_else(|e| {
+        eprintln!("{} (se empieza la campaña de cero)", e);
+        Progress::default()
+    });
+    // Nivel actual de la campaña: el primero sin completar de los desbloqueados
+    let mut current = progress.unlocked(&campaign) - 1;
+    let mut level = or_exit(campaign.load(current));
+
+    let mut scaler = ResolutionScaler::new(render_res);
+    if dynamic {
+        // ~12 ms de render deja margen para 60 FPS
+        scaler = scaler.with_dynamic(0.012);
+    }
+
     let (mut window, raylib_thread) = raylib::init()
         .size(window_width, window_height)
-        .title("Laberinto Verde")
+        .title(&level.name)
         .log_level(TraceLogLevel::LOG_WARNING)
         .build();
 
@@ -40,78 +308,214 @@ fn main() {
     framebuffer.set_background_color(Color::BLACK);
     framebuffer.clear();
 
-    let texman = TextureManager::new(&mut window, &raylib_thread);
-
-    // Cargar mapa desde archivo txt
-    let maze = load_maze("maze.txt");
-
-    // Tamaño de bloque y offsets del mapa en pantalla
-    let (block, offset_x, offset_y) =
-        calc_block_size_offset(&maze, window_width as u32, window_height as u32);
+    let mut texman = TextureManager::new_from(&mut window, &raylib_thread, &level.textures);
 
-    // Elegir spawn en celda libre
-    let (spawn_x, spawn_y) = find_first_free_cell(&maze).unwrap_or((0, 0));
-    
-    // Crear jugador
-    let mut player = Player {
-        pos: Vector2::new(
-            spawn_x as f32 + 0.5, spawn_y as f32 + 0.5
-        ),
-        a: FRAC_PI_4, // angulo de vista inicial (45)
-    };
+    // Jugador en el spawn del nivel y estadisticas de la partida
+    let (mut player, mut health, mut stats, mut enemies) = start_level(&mut level);
+    // semilla del nivel actual si es aleatorio (al completarlo sigue otro aleatorio)
+    let mut random_seed: Option<u64> = None;
 
-    let mut sprites_list = vec![
-        Sprite {
-            pos: Vector2::new(spawn_x as f32 + 3.5, spawn_y as f32 + 1.5), 
-            tex_keys: vec!['c', 'a'],
-            current_frame: 0,
-            frame_time: 0.1,
-            timer: 0.0,
-            size: 1.0,
-        }
-    ];
+    window.set_target_fps(60);
 
-    render_maze(&mut framebuffer, &maze, block, offset_x, offset_y);
-    
-    window.set_target_fps(15);
+    // Camara compartida por la vista 3D, los sprites y el minimapa (FOV de 60 grados)
+    let mut camera = Camera::new(DEFAULT_FOV, framebuffer.width(), framebuffer.height());
+    scaler.apply(&mut framebuffer, &mut camera, window_width as u32, window_height as u32);
 
     enum GameState {
     StartMenu,
+    // Lista de niveles desbloqueados de la campaña
+    LevelSelect,
     Playing,
     Paused,
+    // El jugador llego a la salida; se muestran las estadisticas del nivel
+    LevelComplete,
+    // Se completo el ultimo nivel de la campaña
+    CampaignComplete,
+    // Los perros dejaron al jugador sin salud ni vidas
+    GameOver,
 }
 
 let mut state = GameState::StartMenu;
 
+// Campos de distancia hacia la salida para la pista (H)
+let mut paths = PathCache::new();
+// Nivel marcado en la pantalla de seleccion
+let mut selected = current;
+
 while !window.window_should_close() {
     let dt = window.get_frame_time();
+    // Nivel a empezar al terminar el frame (el cambio de texturas necesita la ventana)
+    let mut next_level: Option<Level> = None;
 
     match state {
         GameState::StartMenu => {
             let mut d = window.begin_drawing(&raylib_thread);
             d.clear_background(Color::BLACK);
-            d.draw_text("Presiona ENTER para iniciar", 100, 200, 30, Color::WHITE);
+            d.draw_text(&campaign.name, 100, 120, 40, Color::GREEN);
+            let continue_text = format!("Nivel {}/{}: {}", current + 1, campaign.len(), campaign.entries[current].name);
+            d.draw_text(&continue_text, 100, 170, 24, Color::GRAY);
+            d.draw_text("Presiona ENTER para iniciar", 100, 220, 30, Color::WHITE);
+            d.draw_text("S: elegir nivel", 100, 270, 20, Color::GRAY);
+            d.draw_text("R: nuevo laberinto aleatorio", 100, 300, 20, Color::GRAY);
             if d.is_key_pressed(KeyboardKey::KEY_ENTER) {
-                state = GameState::Playing;
+                match campaign.load(current) {
+                    Ok(l) => {
+                        random_seed = None;
+                        next_level = Some(l);
+                    }
+                    Err(e) => eprintln!("{}", e),
+                }
+            }
+            if d.is_key_pressed(KeyboardKey::KEY_S) {
+                selected = current;
+                state = GameState::LevelSelect;
+            }
+            if d.is_key_pressed(KeyboardKey::KEY_R) {
+                let seed = time_seed();
+                random_seed = Some(seed);
+                next_level = Some(random_level(seed));
+            }
+        }
+
+        GameState::LevelSelect => {
+            let unlocked = progress.unlocked(&campaign);
+            let mut d = window.begin_drawing(&raylib_thread);
+            d.clear_background(Color::BLACK);
+            d.draw_text("Elegir nivel", 100, 60, 40, Color::GREEN);
+            for (i, entry) in campaign.entries.iter().enumerate() {
+                let y = 120 + i as i32 * 34;
+                let text = match progress.completed.get(&entry.name) {
+                    Some(record) => format!("{}. {}  ({}, {} pasos)", i + 1, entry.name, format_time(record.best_time), record.best_steps),
+                    None if i < unlocked => format!("{}. {}", i + 1, entry.name),
+                    None => format!("{}. {}  (bloqueado)", i + 1, entry.name),
+                };
+                let color = if i == selected {
+                    Color::YELLOW
+                } else if i < unlocked {
+                    Color::WHITE
+                } else {
+                    Color::DARKGRAY
+                };
+                d.draw_text(&text, 100, y, 26, color);
+            }
+            let help_y = 140 + campaign.len() as i32 * 34;
+            d.draw_text("ARRIBA/ABAJO: elegir  ENTER: jugar  BACKSPACE: volver", 100, help_y, 20, Color::GRAY);
+
+            // solo se puede marcar un nivel desbloqueado
+            if d.is_key_pressed(KeyboardKey::KEY_UP) && selected > 0 { selected -= 1; }
+            if d.is_key_pressed(KeyboardKey::KEY_DOWN) && selected + 1 < unlocked { selected += 1; }
+            if d.is_key_pressed(KeyboardKey::KEY_BACKSPACE) { state = GameState::StartMenu; }
+            if d.is_key_pressed(KeyboardKey::KEY_ENTER) {
+                match campaign.load(selected) {
+                    Ok(l) => {
+                        current = selected;
+                        random_seed = None;
+                        next_level = Some(l);
+                    }
+                    Err(e) => eprintln!("{}", e),
+                }
             }
         }
             GameState::Playing => {
-                for s in sprites_list.iter_mut() { s.update(dt); }
-                process_input(&window, &mut player, &maze, dt);
+                // puertas: se abren con el jugador (y sus llaves) o un perro cerca
+                let dogs: Vec<Vector2> = enemies.iter().map(|e| level.sprites[e.sprite].pos).collect();
+                let locked = level.doors.iter().filter(|(_, d)| d.is_locked()).count();
+                level.doors.update(player.pos, &player.inventory.keys, &dogs, dt);
+                // el camino de la pista cambia cuando se abre una cerradura
+                if level.doors.iter().filter(|(_, d)| d.is_locked()).count() != locked {
+                    paths.clear();
+                }
+                update_items(&mut level.items, &level.maze, &level.doors, &level.terrain, dt);
+                update_enemies(&mut enemies, &mut level.sprites, &level.maze, &level.doors, &level.terrain, player.pos, &mut level.items, dt);
+
+                // contacto con los perros: daño, invulnerabilidad breve y, sin salud,
+                // una vida menos (vuelta al spawn) o fin de la partida
+                health.update(dt);
+                if let Some(damage) = contact_damage(&enemies, &level.sprites, player.pos) {
+                    health.hit(damage);
+                }
+                if health.is_dead() {
+                    if health.lose_life() {
+                        // vuelve al spawn con lo que llevaba
+                        player.pos = level.spawn;
+                        player.a = level.spawn_angle;
+                    } else {
+                        state = GameState::GameOver;
+                    }
+                }
+                for s in level.sprites.iter_mut() { s.update(dt); }
+                let prev_pos = player.pos;
+                process_input(&window, &mut player, &level.maze, &level.doors, &level.terrain, dt);
+                stats.record(prev_pos, player.pos, dt);
+
+                // objetos: se juntan al pasar por encima; Q elige, F lanza y G suelta
+                pick_up_items(&mut level.items, player.pos, &mut player.inventory);
+                if window.is_key_pressed(KeyboardKey::KEY_Q) { player.inventory.cycle(); }
+                let throw = window.is_key_pressed(KeyboardKey::KEY_F);
+                if (throw || window.is_key_pressed(KeyboardKey::KEY_G))
+                    && let Some(kind) = player.inventory.take_selected()
+                {
+                    let speed = if throw { THROW_SPEED } else { 0.0 };
+                    level.items.push(launch_item(kind, player.pos, player.a, speed));
+                }
+                process_camera_input(&window, &mut camera, dt);
+                camera.follow(&player);
+                // la vista sube y baja los escalones de a poco
+                let floor = level.terrain.floor_at(player.pos);
+                camera.z += (floor - camera.z) * (STEP_EASE * dt).min(1.0);
+
+                // La resolucion interna se ajusta a la ventana (y al costo si es dinamica)
+                let (win_w, win_h) = (window.get_screen_width() as u32, window.get_screen_height() as u32);
+                scaler.apply(&mut framebuffer, &mut camera, win_w, win_h);
+
+                let render_start = Instant::now();
                 framebuffer.clear();
-                let zbuf = render3d(&mut framebuffer, &maze, &player, block as usize, offset_x, offset_y, &texman);
-                draw_sprites(&mut framebuffer, &player, &sprites_list, block as usize, offset_x, offset_y, &texman, &zbuf);
+                let zbuf = render3d(&mut framebuffer, &level.maze, &level.doors, &level.terrain, &camera, &texman, threads);
+                let sprites = level.sprites.iter().chain(level.items.iter().map(|i| &i.sprite));
+                draw_sprites(&mut framebuffer, &camera, &level.terrain, sprites, &texman, &zbuf);
+                // destello rojo que se apaga durante la invulnerabilidad
+                framebuffer.tint(Color::RED, 0.5 * health.invulnerable / INVULNERABLE_TIME);
+                scaler.update(render_start.elapsed().as_secs_f32(), dt);
 
                 let mut d = window.begin_drawing(&raylib_thread);
                 d.clear_background(Color::SKYBLUE);
-                framebuffer.draw_maze(&mut d, &raylib_thread);
-                let px = (offset_x as f32 + player.pos.x * block as f32) as i32;
-                let py = (offset_y as f32 + player.pos.y * block as f32) as i32;
-                framebuffer.draw_player(px, py);
-                draw_minimap(&mut d, &mut framebuffer, &maze, &player, &sprites_list, 10, 8, block as usize, offset_x, offset_y);
+                framebuffer.present(&mut d, &raylib_thread);
+                let sprites = level.sprites.iter().chain(level.items.iter().map(|i| &i.sprite));
+                draw_minimap(&mut d, &level.maze, &level.doors, &player, &camera, sprites, 10, 8);
+                draw_health(&mut d, &health);
+                draw_inventory(&mut d, &player.inventory);
+                // mantener H muestra el camino mas corto hasta la salida
+                if d.is_key_down(KeyboardKey::KEY_H) {
+                    let (maze, doors, terrain) = (&level.maze, &level.doors, &level.terrain);
+                    let hint = cell_of(player.pos.x, player.pos.y)
+                        .and_then(|cell| paths.to_goal(maze, doors, terrain).path_from(maze, doors, terrain, cell));
+                    if let Some(path) = hint {
+                        draw_minimap_path(&mut d, &path, 10, 8);
+                    }
+                }
                 d.draw_fps(d.get_screen_width() - 100, 10);
+                let res_text = format!("{}x{}", framebuffer.width(), framebuffer.height());
+                d.draw_text(&res_text, d.get_screen_width() - 100, 32, 16, Color::WHITE);
 
                 if d.is_key_pressed(KeyboardKey::KEY_P) { state = GameState::Paused; }
+                // entrar en la salida termina el nivel (y lo guarda si es de la campaña)
+                if on_goal(&level.maze, player.pos) {
+                    if random_seed.is_none() {
+                        progress.record(&campaign.entries[current].name, &stats);
+                        if let Err(e) = progress.save(PROGRESS_FILE) {
+                            eprintln!("No se pudo guardar el progreso en {}: {}", PROGRESS_FILE, e);
+                        }
+                    }
+                    state = GameState::LevelComplete;
+                }
+                // L alterna el filtro de escalado (pixelado / suavizado)
+                if d.is_key_pressed(KeyboardKey::KEY_L) {
+                    let filter = match framebuffer.filter() {
+                        ScaleFilter::Nearest => ScaleFilter::Linear,
+                        ScaleFilter::Linear => ScaleFilter::Nearest,
+                    };
+                    framebuffer.set_filter(filter);
+                }
             }
 
             GameState::Paused => {
@@ -121,6 +525,92 @@ while !window.window_should_close() {
                 d.draw_text("Presiona ENTER para reanudar", 100, 300, 30, Color::WHITE);
                 if d.is_key_pressed(KeyboardKey::KEY_ENTER) { state = GameState::Playing; }
             }
+
+            GameState::LevelComplete => {
+                let mut d = window.begin_drawing(&raylib_thread);
+                d.clear_background(Color::BLACK);
+                d.draw_text("Nivel completado", 100, 120, 40, Color::GREEN);
+                d.draw_text(&level.name, 100, 170, 24, Color::GRAY);
+                let time_text = match level.par_time {
+                    Some(par) => format!("Tiempo: {} (par {})", format_time(stats.elapsed), format_time(par)),
+                    None => format!("Tiempo: {}", format_time(stats.elapsed)),
+                };
+                d.draw_text(&time_text, 100, 230, 30, Color::WHITE);
+                d.draw_text(&format!("Pasos: {}", stats.steps), 100, 270, 30, Color::WHITE);
+                d.draw_text(&format!("Distancia: {:.1} celdas", stats.distance), 100, 310, 30, Color::WHITE);
+                d.draw_text("Presiona ENTER para continuar", 100, 380, 30, Color::WHITE);
+
+                if d.is_key_pressed(KeyboardKey::KEY_ENTER) {
+                    // despues de un nivel aleatorio sigue otro; en la campaña, el siguiente
+                    // nivel hasta terminarla
+                    match random_seed {
+                        Some(seed) => {
+                            let seed = seed.wrapping_add(1);
+                            random_seed = Some(seed);
+                            next_level = Some(random_level(seed));
+                        }
+                        None if current + 1 < campaign.len() => match campaign.load(current + 1) {
+                            Ok(l) => {
+                                current += 1;
+                                next_level = Some(l);
+                            }
+                            Err(e) => {
+                                eprintln!("{}", e);
+                                state = GameState::StartMenu;
+                            }
+                        },
+                        None => state = GameState::CampaignComplete,
+                    }
+                }
+            }
+
+            GameState::CampaignComplete => {
+                let mut d = window.begin_drawing(&raylib_thread);
+                d.clear_background(Color::BLACK);
+                d.draw_text("Campaña completada", 100, 200, 40, Color::WHITE);
+                d.draw_text(&campaign.name, 100, 250, 24, Color::GRAY);
+                d.draw_text("Presiona ENTER para volver al menu", 100, 300, 30, Color::WHITE);
+                if d.is_key_pressed(KeyboardKey::KEY_ENTER) {
+                    state = GameState::StartMenu;
+                }
+            }
+
+            GameState::GameOver => {
+                let mut d = window.begin_drawing(&raylib_thread);
+                d.clear_background(Color::BLACK);
+                d.draw_text("Te atraparon los perros", 100, 200, 40, Color::RED);
+                d.draw_text(&level.name, 100, 250, 24, Color::GRAY);
+                d.draw_text("ENTER: reintentar el nivel", 100, 300, 30, Color::WHITE);
+                d.draw_text("BACKSPACE: volver al menu", 100, 340, 30, Color::WHITE);
+                if d.is_key_pressed(KeyboardKey::KEY_ENTER) {
+                    // el nivel se vuelve a cargar para que los perros vuelvan a su lugar
+                    let retry = match random_seed {
+                        Some(seed) => Ok(random_level(seed)),
+                        None => campaign.load(current),
+                    };
+                    match retry {
+                        Ok(l) => next_level = Some(l),
+                        Err(e) => {
+                            eprintln!("{}", e);
+                            state = GameState::StartMenu;
+                        }
+                    }
+                }
+                if d.is_key_pressed(KeyboardKey::KEY_BACKSPACE) { state = GameState::StartMenu; }
+            }
+        }
+
+        // Cambio de nivel sin reiniciar: laberinto, spawn, sprites y texturas
+        if let Some(new_level) = next_level {
+            // solo se vuelven a subir las texturas si el nivel usa otras
+            if new_level.textures != level.textures {
+                texman = TextureManager::new_from(&mut window, &raylib_thread, &new_level.textures);
+            }
+            level = new_level;
+            window.set_window_title(&raylib_thread, &level.name);
+            (player, health, stats, enemies) = start_level(&mut level);
+            paths.clear();
+            state = GameState::Playing;
         }
     }
 }
@@ -1,30 +1,149 @@
 // src/maze.rs
 
 use crate::framebuffer::symbol_to_color;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fmt;
+use std::fs;
+use std::io;
 
-use crate::framebuffer::Framebuffer;
+use crate::doors::is_door;
+use crate::framebuffer::{Framebuffer, MapLayout};
+
+pub mod generate;
+pub mod metrics;
 
 pub type Maze = Vec<Vec<char>>;
 
-pub fn load_maze(filename: &str) -> Maze {
-    let file = File::open(filename).expect("Error: Could not open maze file");
-    let reader = BufReader::new(file);
+// Simbolos validos en un archivo de laberinto ('D', 'R', 'B' e 'Y' son puertas,
+// ver doors.rs; '_', '~', '[', ']', 'F', 'W' e 'I' son paredes finas y '#' y 'H'
+// bloques transparentes, ver walls.rs)
+pub const MAZE_SYMBOLS: [char; 18] = [
+    '+', '-', '|', 'g', ' ', 'D', 'R', 'B', 'Y', '_', '~', '[', ']', 'F', 'W', 'I', '#', 'H',
+];
+
+// Problema puntual dentro del archivo (linea y columna empiezan en 1)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseIssue {
+    pub line: usize,
+    pub column: usize,
+    pub kind: IssueKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IssueKind {
+    // Caracter que no corresponde a ninguna pared ni al piso
+    UnknownSymbol(char),
+    // Fila con un ancho distinto al de la primera fila
+    RaggedRow { expected: usize, found: usize },
+}
+
+#[derive(Debug)]
+pub enum MazeError {
+    // No se pudo leer el archivo
+    Io { file: String, source: io::Error },
+    // El archivo no tiene ninguna fila
+    Empty { file: String },
+    // Todos los problemas encontrados, en orden de aparicion
+    Parse { file: String, issues: Vec<ParseIssue> },
+}
+
+impl fmt::Display for ParseIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            IssueKind::UnknownSymbol(c) => write!(
+                f, "linea {}, columna {}: simbolo desconocido {:?}", self.line, self.column, c
+            ),
+            IssueKind::RaggedRow { expected, found } => write!(
+                f, "linea {}, columna {}: la fila tiene {} celdas y se esperaban {}",
+                self.line, self.column, found, expected
+            ),
+        }
+    }
+}
+
+impl fmt::Display for MazeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MazeError::Io { file, source } => write!(f, "{}: no se pudo abrir el laberinto: {}", file, source),
+            MazeError::Empty { file } => write!(f, "{}: el laberinto esta vacio", file),
+            MazeError::Parse { file, issues } => {
+                write!(f, "{}: {} problema(s) en el laberinto", file, issues.len())?;
+                for issue in issues {
+                    write!(f, "\n  {}:{}", file, issue)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
 
-    reader
-        .lines()
-        .map(|line| {
-            line.unwrap()
-                .chars()
-                .filter(|c| matches!(c, '+' | '-' | '|' | 'g' | ' ' ))
-                .collect()
-        })
-        .collect()
+impl std::error::Error for MazeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MazeError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
 }
 
+pub fn load_maze(filename: &str) -> Result<Maze, MazeError> {
+    let text = fs::read_to_string(filename).map_err(|source| MazeError::Io {
+        file: filename.to_string(),
+        source,
+    })?;
+    parse_maze(&text, filename)
+}
+
+// Interpreta el texto de un laberinto. `file` solo se usa en los mensajes de error.
+// Junta todos los problemas en vez de cortar en el primero.
+pub fn parse_maze(text: &str, file: &str) -> Result<Maze, MazeError> {
+    let mut lines: Vec<&str> = text.lines().map(|l| l.trim_end_matches('\r')).collect();
+    // las lineas vacias al final del archivo no son filas
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    if lines.is_empty() {
+        return Err(MazeError::Empty { file: file.to_string() });
+    }
+
+    let expected = lines[0].chars().count();
+    let mut issues = Vec::new();
+    let mut maze = Vec::with_capacity(lines.len());
+
+    for (y, line) in lines.iter().enumerate() {
+        let row: Vec<char> = line.chars().collect();
+        for (x, &c) in row.iter().enumerate() {
+            if !MAZE_SYMBOLS.contains(&c) {
+                issues.push(ParseIssue { line: y + 1, column: x + 1, kind: IssueKind::UnknownSymbol(c) });
+            }
+        }
+        if row.len() != expected {
+            issues.push(ParseIssue {
+                line: y + 1,
+                column: row.len().min(expected) + 1,
+                kind: IssueKind::RaggedRow { expected, found: row.len() },
+            });
+        }
+        maze.push(row);
+    }
+
+    if issues.is_empty() {
+        Ok(maze)
+    } else {
+        Err(MazeError::Parse { file: file.to_string(), issues })
+    }
+}
+
+// Las puertas cuentan como pared; si se pueden atravesar depende de cuanto esten
+// abiertas (controller::cell_is_free). Las rejas, ventanas y pilares tambien son
+// pared aunque no llenen la celda; las paredes de borde no (la celda se pisa).
 pub fn is_wall(c: char) -> bool {
-    matches!(c, '+' | '-' | '|' | 'g')
+    matches!(c, '+' | '-' | '|' | 'g' | 'F' | 'W' | 'I' | '#' | 'H') || is_door(c)
+}
+
+// La salida 'g' se dibuja como pared (la puerta del laberinto) pero se puede
+// atravesar: entrar en ella termina el nivel
+pub fn is_goal(c: char) -> bool {
+    c == 'g'
 }
 
 pub fn find_first_free_cell(maze: &Maze) -> Option<(usize, usize)> {
@@ -41,20 +160,17 @@ pub fn find_first_free_cell(maze: &Maze) -> Option<(usize, usize)> {
 pub fn render_maze(
     framebuffer: &mut Framebuffer,
     maze: &Maze,
-    block_size: u32,
-    offset_x: i32,
-    offset_y: i32,
-
+    layout: &MapLayout,
 ) {
-    let b = block_size as i32;
+    let b = layout.block_size as i32;
     
     for (y, row) in maze.iter().enumerate() {
         for (x, &cell) in row.iter().enumerate() {
             let color = symbol_to_color(cell);
 
-            let px = (offset_x + (x as i32) * b) as u32;
-            let py = (offset_y + (y as i32) * b) as u32;
-            framebuffer.set_thick_pixel(px, py, block_size, color);
+            let px = (layout.offset_x + (x as i32) * b) as u32;
+            let py = (layout.offset_y + (y as i32) * b) as u32;
+            framebuffer.set_thick_pixel(px, py, layout.block_size, color);
         }
     }
 }
\ No newline at end of file
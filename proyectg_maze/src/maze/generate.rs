@@ -0,0 +1,497 @@
+// src/maze/generate.rs
+// Generador de laberintos perfectos (un unico camino entre dos celdas cualquiera)
+// con la convencion de maze.txt: '+' en las esquinas, '-' y '|' en las paredes y
+// una salida 'g' en el borde. Para la misma semilla el resultado es siempre igual.
+
+use std::collections::VecDeque;
+
+use crate::maze::Maze;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Backtracker,
+    Prim,
+    Kruskal,
+    Wilson,
+    Eller,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 5] = [
+        Algorithm::Backtracker,
+        Algorithm::Prim,
+        Algorithm::Kruskal,
+        Algorithm::Wilson,
+        Algorithm::Eller,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Backtracker => "backtracker",
+            Algorithm::Prim => "prim",
+            Algorithm::Kruskal => "kruskal",
+            Algorithm::Wilson => "wilson",
+            Algorithm::Eller => "eller",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Algorithm> {
+        Algorithm::ALL.into_iter().find(|a| a.name() == name)
+    }
+}
+
+// Generador pseudoaleatorio propio (SplitMix64) para que una semilla de siempre el
+// mismo laberinto sin depender de la version de ninguna biblioteca externa
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Entero en [0, n)
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn chance(&mut self, p: f32) -> bool {
+        let unit = (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32;
+        unit < p
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+// Grilla de celdas con los pasos abiertos hacia el este y hacia el sur
+pub struct Cells {
+    pub width: usize,
+    pub height: usize,
+    east: Vec<bool>,
+    south: Vec<bool>,
+}
+
+impl Cells {
+    pub fn new(width: usize, height: usize) -> Self {
+        Cells {
+            width,
+            height,
+            east: vec![false; width * height],
+            south: vec![false; width * height],
+        }
+    }
+
+    fn index(&self, x: usize, y: usize) -> usize {
+        y * self.width + x
+    }
+
+    // Abre el paso entre dos celdas vecinas
+    pub fn carve(&mut self, a: (usize, usize), b: (usize, usize)) {
+        let (a, b) = if (a.1, a.0) < (b.1, b.0) { (a, b) } else { (b, a) };
+        let i = self.index(a.0, a.1);
+        if a.1 == b.1 { self.east[i] = true; } else { self.south[i] = true; }
+    }
+
+    pub fn is_open(&self, a: (usize, usize), b: (usize, usize)) -> bool {
+        let (a, b) = if (a.1, a.0) < (b.1, b.0) { (a, b) } else { (b, a) };
+        let i = self.index(a.0, a.1);
+        if a.1 == b.1 { self.east[i] } else { self.south[i] }
+    }
+
+    pub fn neighbors(&self, (x, y): (usize, usize)) -> Vec<(usize, usize)> {
+        let mut n = Vec::with_capacity(4);
+        if x > 0 { n.push((x - 1, y)); }
+        if y > 0 { n.push((x, y - 1)); }
+        if x + 1 < self.width { n.push((x + 1, y)); }
+        if y + 1 < self.height { n.push((x, y + 1)); }
+        n
+    }
+
+    // Lleva las celdas a caracteres: la celda (x, y) queda en (2x+1, 2y+1)
+    pub fn to_maze(&self) -> Maze {
+        let (w, h) = (self.width * 2 + 1, self.height * 2 + 1);
+        let mut maze = vec![vec![' '; w]; h];
+        for (y, row) in maze.iter_mut().enumerate() {
+            for (x, c) in row.iter_mut().enumerate() {
+                *c = match (x % 2, y % 2) {
+                    (0, 0) => '+',
+                    (1, 0) => '-',
+                    (0, 1) => '|',
+                    _ => ' ',
+                };
+            }
+        }
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let i = self.index(x, y);
+                if self.east[i] { maze[2 * y + 1][2 * x + 2] = ' '; }
+                if self.south[i] { maze[2 * y + 2][2 * x + 1] = ' '; }
+            }
+        }
+        maze
+    }
+}
+
+// Modificaciones opcionales sobre el laberinto perfecto. Todas solo abren paredes,
+// asi que el resultado sigue siendo soluble.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GenOptions {
+    // Fraccion (0..1) de callejones sin salida que se abren para formar ciclos
+    pub braid: f32,
+    // Salas rectangulares despejadas de 2 a 4 celdas por lado
+    pub rooms: usize,
+    // Plazas grandes (hasta un tercio del mapa) con columnas '+' sueltas
+    pub plazas: usize,
+    // Pasillos rectos de media longitud del mapa
+    pub corridors: usize,
+}
+
+// Genera un laberinto de `width` x `height` celdas (el mapa resultante mide
+// 2*width+1 x 2*height+1 caracteres). El spawn queda arriba a la izquierda y la
+// salida 'g' en el borde, junto a la celda de borde mas lejana al spawn.
+pub fn generate(width: usize, height: usize, algorithm: Algorithm, seed: u64) -> Maze {
+    generate_with(width, height, algorithm, seed, &GenOptions::default())
+}
+
+pub fn generate_with(
+    width: usize,
+    height: usize,
+    algorithm: Algorithm,
+    seed: u64,
+    options: &GenOptions,
+) -> Maze {
+    let mut rng = Rng::new(seed);
+    let mut cells = Cells::new(width.max(1), height.max(1));
+    run_algorithm(&mut cells, &mut rng, algorithm);
+
+    let rooms: Vec<Rect> = (0..options.rooms).map(|_| carve_room(&mut cells, &mut rng, 2, 4)).collect();
+    for _ in 0..options.plazas {
+        let max = (cells.width.max(cells.height) / 3).max(3);
+        carve_room(&mut cells, &mut rng, 3, max);
+    }
+    for _ in 0..options.corridors {
+        carve_corridor(&mut cells, &mut rng);
+    }
+    if options.braid > 0.0 {
+        braid(&mut cells, &mut rng, options.braid);
+    }
+
+    let mut maze = cells.to_maze();
+    // en las salas se quitan tambien las columnas de las esquinas interiores
+    for r in rooms {
+        for y in r.y..r.y + r.h - 1 {
+            for x in r.x..r.x + r.w - 1 {
+                maze[2 * y + 2][2 * x + 2] = ' ';
+            }
+        }
+    }
+    place_exit(&cells, &mut maze);
+    maze
+}
+
+pub fn generate_cells(width: usize, height: usize, algorithm: Algorithm, seed: u64) -> Cells {
+    let mut cells = Cells::new(width, height);
+    run_algorithm(&mut cells, &mut Rng::new(seed), algorithm);
+    cells
+}
+
+fn run_algorithm(cells: &mut Cells, rng: &mut Rng, algorithm: Algorithm) {
+    match algorithm {
+        Algorithm::Backtracker => backtracker(cells, rng),
+        Algorithm::Prim => prim(cells, rng),
+        Algorithm::Kruskal => kruskal(cells, rng),
+        Algorithm::Wilson => wilson(cells, rng),
+        Algorithm::Eller => eller(cells, rng),
+    }
+}
+
+// Busqueda en profundidad con pila: pasillos largos y pocas bifurcaciones
+fn backtracker(cells: &mut Cells, rng: &mut Rng) {
+    let mut visited = vec![false; cells.width * cells.height];
+    let mut stack = vec![(0, 0)];
+    visited[0] = true;
+
+    while let Some(&current) = stack.last() {
+        let options: Vec<_> = cells
+            .neighbors(current)
+            .into_iter()
+            .filter(|&(x, y)| !visited[cells.index(x, y)])
+            .collect();
+        if options.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let next = options[rng.below(options.len())];
+        visited[cells.index(next.0, next.1)] = true;
+        cells.carve(current, next);
+        stack.push(next);
+    }
+}
+
+// Prim aleatorio: crece desde una celda tomando pasos al azar de la frontera
+fn prim(cells: &mut Cells, rng: &mut Rng) {
+    let mut inside = vec![false; cells.width * cells.height];
+    let start = (rng.below(cells.width), rng.below(cells.height));
+    inside[cells.index(start.0, start.1)] = true;
+    let mut frontier: Vec<((usize, usize), (usize, usize))> =
+        cells.neighbors(start).into_iter().map(|n| (start, n)).collect();
+
+    while !frontier.is_empty() {
+        let (from, to) = frontier.swap_remove(rng.below(frontier.len()));
+        let i = cells.index(to.0, to.1);
+        if inside[i] { continue; }
+        inside[i] = true;
+        cells.carve(from, to);
+        for n in cells.neighbors(to) {
+            if !inside[cells.index(n.0, n.1)] {
+                frontier.push((to, n));
+            }
+        }
+    }
+}
+
+// Conjuntos disjuntos para Kruskal y Eller
+struct DisjointSet {
+    parent: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(n: usize) -> Self {
+        DisjointSet { parent: (0..n).collect() }
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    // Une dos conjuntos; devuelve false si ya eran el mismo
+    fn union(&mut self, a: usize, b: usize) -> bool {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra == rb { return false; }
+        self.parent[rb] = ra;
+        true
+    }
+}
+
+// Kruskal aleatorio: recorre todas las paredes mezcladas y abre las que unen
+// dos regiones distintas
+fn kruskal(cells: &mut Cells, rng: &mut Rng) {
+    let mut edges = Vec::new();
+    for y in 0..cells.height {
+        for x in 0..cells.width {
+            if x + 1 < cells.width { edges.push(((x, y), (x + 1, y))); }
+            if y + 1 < cells.height { edges.push(((x, y), (x, y + 1))); }
+        }
+    }
+    rng.shuffle(&mut edges);
+
+    let mut sets = DisjointSet::new(cells.width * cells.height);
+    for (a, b) in edges {
+        if sets.union(cells.index(a.0, a.1), cells.index(b.0, b.1)) {
+            cells.carve(a, b);
+        }
+    }
+}
+
+// Wilson: caminatas aleatorias sin ciclos hasta tocar el laberinto ya armado;
+// produce una muestra uniforme de todos los laberintos posibles
+fn wilson(cells: &mut Cells, rng: &mut Rng) {
+    let n = cells.width * cells.height;
+    let mut inside = vec![false; n];
+    inside[rng.below(n)] = true;
+    // direccion de salida de cada celda durante la caminata actual
+    let mut next: Vec<Option<(usize, usize)>> = vec![None; n];
+
+    for start in 0..n {
+        if inside[start] { continue; }
+        let start = (start % cells.width, start / cells.width);
+
+        // caminar hasta el laberinto; pisar una celda de nuevo borra el ciclo
+        let mut cell = start;
+        while !inside[cells.index(cell.0, cell.1)] {
+            let options = cells.neighbors(cell);
+            let step = options[rng.below(options.len())];
+            next[cells.index(cell.0, cell.1)] = Some(step);
+            cell = step;
+        }
+
+        // agregar el camino sin ciclos
+        let mut cell = start;
+        while !inside[cells.index(cell.0, cell.1)] {
+            let i = cells.index(cell.0, cell.1);
+            let step = next[i].unwrap();
+            inside[i] = true;
+            cells.carve(cell, step);
+            cell = step;
+        }
+    }
+}
+
+// Eller: arma el laberinto fila por fila guardando solo los conjuntos de la fila actual
+fn eller(cells: &mut Cells, rng: &mut Rng) {
+    let (w, h) = (cells.width, cells.height);
+    let mut sets = DisjointSet::new(w * h);
+
+    for y in 0..h {
+        let last = y + 1 == h;
+
+        // unir vecinas horizontales al azar (en la ultima fila, todas las que falten)
+        for x in 0..w.saturating_sub(1) {
+            let (a, b) = (cells.index(x, y), cells.index(x + 1, y));
+            if (last || rng.chance(0.5)) && sets.find(a) != sets.find(b) {
+                sets.union(a, b);
+                cells.carve((x, y), (x + 1, y));
+            }
+        }
+        if last { break; }
+
+        // cada conjunto baja al menos una vez a la fila siguiente
+        let mut groups: Vec<(usize, Vec<usize>)> = Vec::new();
+        for x in 0..w {
+            let root = sets.find(cells.index(x, y));
+            match groups.iter_mut().find(|(r, _)| *r == root) {
+                Some((_, members)) => members.push(x),
+                None => groups.push((root, vec![x])),
+            }
+        }
+        for (_, members) in groups {
+            let forced = members[rng.below(members.len())];
+            for x in members {
+                if x == forced || rng.chance(0.3) {
+                    sets.union(cells.index(x, y), cells.index(x, y + 1));
+                    cells.carve((x, y), (x, y + 1));
+                }
+            }
+        }
+    }
+}
+
+// Rectangulo de celdas
+struct Rect {
+    x: usize,
+    y: usize,
+    w: usize,
+    h: usize,
+}
+
+// Abre todos los pasos dentro de un rectangulo al azar de min..=max celdas por lado
+fn carve_room(cells: &mut Cells, rng: &mut Rng, min: usize, max: usize) -> Rect {
+    let mut side = |limit: usize| {
+        let hi = max.min(limit);
+        let lo = min.min(hi);
+        lo + rng.below(hi - lo + 1)
+    };
+    let (w, h) = (side(cells.width), side(cells.height));
+    let x = rng.below(cells.width - w + 1);
+    let y = rng.below(cells.height - h + 1);
+
+    for cy in y..y + h {
+        for cx in x..x + w {
+            if cx + 1 < x + w { cells.carve((cx, cy), (cx + 1, cy)); }
+            if cy + 1 < y + h { cells.carve((cx, cy), (cx, cy + 1)); }
+        }
+    }
+    Rect { x, y, w, h }
+}
+
+// Abre un pasillo recto horizontal o vertical de la mitad del mapa
+fn carve_corridor(cells: &mut Cells, rng: &mut Rng) {
+    let horizontal = rng.chance(0.5);
+    let (along, across) = if horizontal { (cells.width, cells.height) } else { (cells.height, cells.width) };
+    let len = (along / 2).max(2).min(along);
+    let start = rng.below(along - len + 1);
+    let line = rng.below(across);
+
+    for k in start..start + len - 1 {
+        if horizontal {
+            cells.carve((k, line), (k + 1, line));
+        } else {
+            cells.carve((line, k), (line, k + 1));
+        }
+    }
+}
+
+fn open_count(cells: &Cells, c: (usize, usize)) -> usize {
+    cells.neighbors(c).into_iter().filter(|&n| cells.is_open(c, n)).count()
+}
+
+// Abre una pared de `fraction` de los callejones sin salida; se prefiere unir dos
+// callejones entre si para eliminar ambos con un solo paso
+fn braid(cells: &mut Cells, rng: &mut Rng, fraction: f32) {
+    let mut ends: Vec<(usize, usize)> = (0..cells.height)
+        .flat_map(|y| (0..cells.width).map(move |x| (x, y)))
+        .filter(|&c| open_count(cells, c) == 1)
+        .collect();
+    rng.shuffle(&mut ends);
+    let count = (ends.len() as f32 * fraction.clamp(0.0, 1.0)).round() as usize;
+
+    for &cell in ends.iter().take(count) {
+        // ya no es callejon si lo abrio la union con otro
+        if open_count(cells, cell) != 1 { continue; }
+        let closed: Vec<_> = cells.neighbors(cell).into_iter().filter(|&n| !cells.is_open(cell, n)).collect();
+        if closed.is_empty() { continue; }
+
+        let partner = closed.iter().copied().find(|&n| open_count(cells, n) == 1);
+        let target = partner.unwrap_or_else(|| closed[rng.below(closed.len())]);
+        cells.carve(cell, target);
+    }
+}
+
+// Pone la salida en la pared exterior de la celda de borde mas lejana a (0, 0)
+fn place_exit(cells: &Cells, maze: &mut Maze) {
+    let (w, h) = (cells.width, cells.height);
+    let mut dist = vec![usize::MAX; w * h];
+    let mut queue = VecDeque::from([(0, 0)]);
+    dist[0] = 0;
+    while let Some(c) = queue.pop_front() {
+        let d = dist[cells.index(c.0, c.1)];
+        for n in cells.neighbors(c) {
+            let i = cells.index(n.0, n.1);
+            if dist[i] == usize::MAX && cells.is_open(c, n) {
+                dist[i] = d + 1;
+                queue.push_back(n);
+            }
+        }
+    }
+
+    // entre las celdas de borde gana la mas lejana; a igual distancia, la primera
+    let mut best = ((0, 0), 0);
+    for y in 0..h {
+        for x in 0..w {
+            let on_border = x == 0 || y == 0 || x + 1 == w || y + 1 == h;
+            let d = dist[cells.index(x, y)];
+            if on_border && d != usize::MAX && d > best.1 {
+                best = ((x, y), d);
+            }
+        }
+    }
+
+    // la pared exterior de esa celda (preferencia: este, sur, oeste, norte)
+    let ((x, y), _) = best;
+    let (mx, my) = (2 * x + 1, 2 * y + 1);
+    let (gx, gy) = if x + 1 == w {
+        (mx + 1, my)
+    } else if y + 1 == h {
+        (mx, my + 1)
+    } else if x == 0 {
+        (mx - 1, my)
+    } else {
+        (mx, my - 1)
+    };
+    maze[gy][gx] = 'g';
+}
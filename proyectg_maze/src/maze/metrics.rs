@@ -0,0 +1,87 @@
+// src/maze/metrics.rs
+// Metricas de dificultad de un laberinto, para comparar niveles y ajustar una campaña.
+// Trabaja sobre la grilla de caracteres, asi que sirve para niveles escritos a mano
+// y para los generados.
+
+use std::fmt;
+
+use crate::doors::Doors;
+use crate::maze::{Maze, find_first_free_cell, is_wall};
+use crate::pathfinding::{distance_field, goal_cells, walkable_neighbors};
+use crate::terrain::Terrain;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Difficulty {
+    // Pasos (celdas del mapa) del spawn a la salida; None si no se llega
+    pub solution_length: Option<usize>,
+    // Celdas libres con una sola vecina libre
+    pub dead_ends: usize,
+    // Celdas libres con tres o mas vecinas libres
+    pub junctions: usize,
+    // Cruces que aparecen sobre el camino de la solucion
+    pub decisions: usize,
+    // Promedio de salidas extra (vecinas libres - 2) en los cruces del mapa
+    pub branching_factor: f32,
+    pub free_cells: usize,
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.solution_length {
+            Some(n) => write!(f, "solucion {} pasos", n)?,
+            None => write!(f, "sin solucion")?,
+        }
+        write!(
+            f,
+            ", {} callejones, {} cruces ({} en la solucion), ramificacion {:.2}, {} celdas libres",
+            self.dead_ends, self.junctions, self.decisions, self.branching_factor, self.free_cells
+        )
+    }
+}
+
+// Vecinas libres de una celda (la salida no cuenta como pasillo)
+fn open_degree(maze: &Maze, cell: (usize, usize)) -> usize {
+    walkable_neighbors(maze, &Doors::NONE, &Terrain::FLAT, cell).filter(|&(x, y)| !is_wall(maze[y][x])).count()
+}
+
+pub fn difficulty(maze: &Maze) -> Difficulty {
+    let mut dead_ends = 0;
+    let mut junctions = 0;
+    let mut extra_exits = 0;
+    let mut free_cells = 0;
+    for (y, row) in maze.iter().enumerate() {
+        for (x, &c) in row.iter().enumerate() {
+            if is_wall(c) { continue; }
+            free_cells += 1;
+            let degree = open_degree(maze, (x, y));
+            if degree == 1 { dead_ends += 1; }
+            if degree >= 3 {
+                junctions += 1;
+                extra_exits += degree - 2;
+            }
+        }
+    }
+
+    // Camino de la solucion siguiendo el campo de distancia hacia las salidas
+    let mut solution_length = None;
+    let mut decisions = 0;
+    if let Some(spawn) = find_first_free_cell(maze) {
+        let to_goal = distance_field(maze, &Doors::NONE, &Terrain::FLAT, &goal_cells(maze));
+        if let Some(path) = to_goal.path_from(maze, &Doors::NONE, &Terrain::FLAT, spawn) {
+            solution_length = Some(path.len() - 1);
+            decisions = path
+                .iter()
+                .filter(|&&(x, y)| !is_wall(maze[y][x]) && open_degree(maze, (x, y)) >= 3)
+                .count();
+        }
+    }
+
+    Difficulty {
+        solution_length,
+        dead_ends,
+        junctions,
+        decisions,
+        branching_factor: if junctions > 0 { extra_exits as f32 / junctions as f32 } else { 0.0 },
+        free_cells,
+    }
+}
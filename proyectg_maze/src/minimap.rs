@@ -1,24 +1,27 @@
 // src/minimap.rs
 use raylib::prelude::*;
 
-use crate::framebuffer::{symbol_to_color, Framebuffer};
+use crate::framebuffer::symbol_to_color;
 use crate::maze::Maze;
 use crate::player::Player;
 use crate::sprites::Sprite;
-use crate::caster::cast_ray;
+use crate::caster::{Face, cast_ray_dir};
+use crate::camera::Camera;
+use crate::doors::Doors;
+use crate::pathfinding::Cell;
+use crate::walls::{PILLAR_HALF, WallShape, wall_shape};
 
 // Establece el minimapa en la esquina superior izquierda
-pub fn draw_minimap(
+#[allow(clippy::too_many_arguments)]
+pub fn draw_minimap<'a>(
     d: &mut RaylibDrawHandle,
-    framebuffer: &mut Framebuffer,
     maze: &Maze,
+    doors: &Doors,
     player: &Player,
-    sprites: &[Sprite],
+    camera: &Camera,
+    sprites: impl IntoIterator<Item = &'a Sprite>,
     tile_px: i32,
     margin: i32,
-    block_size: usize,
-    offset_x: i32,
-    offset_y: i32,
 ) {
     let tile_px = tile_px.max(2);
     let x0 = margin;
@@ -31,17 +34,40 @@ pub fn draw_minimap(
     d.draw_rectangle(x0 - 2, y0 - 2, map_w + 4, map_h + 4, Color::new(0, 0, 0, 160));
     d.draw_rectangle_lines(x0 - 2, y0 - 2, map_w + 4, map_h + 4, Color::GREEN);
 
-    // celdas implementando symbol_to_color
+    // celdas implementando symbol_to_color; las puertas se aclaran hacia el color
+    // del piso a medida que se abren
     for (ry, row) in maze.iter().enumerate() {
         for (rx, &cell) in row.iter().enumerate() {
-            let c = symbol_to_color(cell);
-            d.draw_rectangle(
-                x0 + (rx as i32) * tile_px,
-                y0 + (ry as i32) * tile_px,
-                tile_px,
-                tile_px,
-                c,
-            );
+            let mut c = symbol_to_color(cell);
+            let (cx, cy) = (x0 + (rx as i32) * tile_px, y0 + (ry as i32) * tile_px);
+            if let Some(door) = doors.get((rx, ry)) {
+                c = c.lerp(symbol_to_color(' '), door.open);
+                d.draw_rectangle(cx, cy, tile_px, tile_px, c);
+                continue;
+            }
+
+            // las paredes finas se dibujan sobre el piso con su forma
+            let thick = (tile_px / 4).max(1);
+            let part = match wall_shape(maze, (rx, ry), cell) {
+                WallShape::Block => None,
+                WallShape::Edge(Face::North) => Some((cx, cy, tile_px, thick)),
+                WallShape::Edge(Face::South) => Some((cx, cy + tile_px - thick, tile_px, thick)),
+                WallShape::Edge(Face::West) => Some((cx, cy, thick, tile_px)),
+                WallShape::Edge(Face::East) => Some((cx + tile_px - thick, cy, thick, tile_px)),
+                WallShape::Panel { along_x: true } => Some((cx, cy + (tile_px - thick) / 2, tile_px, thick)),
+                WallShape::Panel { along_x: false } => Some((cx + (tile_px - thick) / 2, cy, thick, tile_px)),
+                WallShape::Pillar => {
+                    let side = ((2.0 * PILLAR_HALF * tile_px as f32) as i32).max(1);
+                    Some((cx + (tile_px - side) / 2, cy + (tile_px - side) / 2, side, side))
+                }
+            };
+            match part {
+                None => d.draw_rectangle(cx, cy, tile_px, tile_px, c),
+                Some((px, py, w, h)) => {
+                    d.draw_rectangle(cx, cy, tile_px, tile_px, symbol_to_color(' '));
+                    d.draw_rectangle(px, py, w, h, c);
+                }
+            }
         }
     }
 
@@ -50,39 +76,26 @@ pub fn draw_minimap(
     let py = y0 as f32 + player.pos.y * tile_px as f32;
     d.draw_circle(px as i32, py as i32, ((tile_px as f32) * 0.45).max(2.0), Color::INDIGO);
 
-    // FOV con rayos usando cast_ray
-    let fov = 1.047; // 60
+    // FOV con rayos usando el mismo plano de camara que la vista 3D
     let rays = 60;   // numero de rayos
-    let b = block_size as f32;
 
     for i in 0..rays {
         let t = i as f32 / (rays - 1) as f32;
-        let a = player.a - (fov * 0.5) + (fov * t);
-
-        if let Some(hit) = cast_ray(
-            framebuffer,
-            maze,
-            player,
-            a,
-            block_size,
-            offset_x,
-            offset_y,
-            false,
-        ) {
-            // punto de impacto en coordenadas de CELDA (mundo 2D):
-            let mx = (hit.hit_x - offset_x as f32) / b;
-            let my = (hit.hit_y - offset_y as f32) / b;
-
-            // llevar a coords del minimapa
-            let ex = x0 as f32 + mx * tile_px as f32;
-            let ey = y0 as f32 + my * tile_px as f32;
+        let ray = camera.ray_through(2.0 * t - 1.0);
+
+        if let Some(hit) = cast_ray_dir(maze, doors, camera.pos, ray) {
+            // el impacto ya viene en coordenadas de CELDA (mundo 2D),
+            // solo hay que llevarlo a coords del minimapa
+            let ex = x0 as f32 + hit.hit_x * tile_px as f32;
+            let ey = y0 as f32 + hit.hit_y * tile_px as f32;
 
             // linea del jugador hacia el impacto
             d.draw_line(px as i32, py as i32, ex as i32, ey as i32, Color::WHITE);
         } else {
             // si no choca (salió del mapa), traza un segmento corto en dirección
-            let ex = px + (tile_px as f32) * 2.0 * a.cos();
-            let ey = py + (tile_px as f32) * 2.0 * a.sin();
+            let dir = ray.normalized();
+            let ex = px + (tile_px as f32) * 2.0 * dir.x;
+            let ey = py + (tile_px as f32) * 2.0 * dir.y;
             d.draw_line(px as i32, py as i32, ex as i32, ey as i32, Color::WHITE);
         }
     }
@@ -104,4 +117,20 @@ pub fn draw_minimap(
     let dx = px + len * player.a.cos();
     let dy = py + len * player.a.sin();
     d.draw_line(px as i32, py as i32, dx as i32, dy as i32, Color::SKYBLUE);
-}
\ No newline at end of file
+}
+
+// Pista: dibuja sobre el minimapa un camino de celdas (por ejemplo hacia la salida)
+pub fn draw_minimap_path(d: &mut RaylibDrawHandle, path: &[Cell], tile_px: i32, margin: i32) {
+    let tile_px = tile_px.max(2);
+    let center = |(x, y): Cell| (margin + x as i32 * tile_px + tile_px / 2, margin + y as i32 * tile_px + tile_px / 2);
+    for pair in path.windows(2) {
+        let (ax, ay) = center(pair[0]);
+        let (bx, by) = center(pair[1]);
+        d.draw_line_ex(
+            Vector2::new(ax as f32, ay as f32),
+            Vector2::new(bx as f32, by as f32),
+            2.0,
+            Color::YELLOW,
+        );
+    }
+}
@@ -0,0 +1,256 @@
+// src/pathfinding.rs
+// Navegacion sobre la grilla del laberinto: campos de distancia por BFS, camino
+// mas corto con A* y un cache de campos hacia la salida. Una celda es transitable
+// con la misma regla que usa el jugador (controller::cell_is_free) y los pasos son
+// en las cuatro direcciones, igual que el deslizamiento por ejes del movimiento.
+// Las puertas corredizas cuentan como abiertas: el camino pasa por ellas aunque
+// haya que esperar a que se abran. Las que siguen con cerradura en `doors` cortan el
+// paso (con Doors::NONE no hay ninguna). Las paredes de borde (walls.rs) cortan el
+// paso entre dos celdas libres, y los escalones mas altos que terrain::MAX_STEP solo
+// se bajan, como le pasa al jugador.
+
+use raylib::prelude::Vector2;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+use crate::controller::{can_step, cell_is_free};
+use crate::doors::{Doors, KeyColor};
+use crate::maze::Maze;
+use crate::terrain::Terrain;
+use crate::walls::edge_between;
+
+// Celda como (columna, fila)
+pub type Cell = (usize, usize);
+
+const STEPS: [(isize, isize); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+// true si la celda es una puerta que todavia tiene la cerradura puesta
+fn is_locked(doors: &Doors, cell: Cell) -> bool {
+    doors.get(cell).is_some_and(|d| d.is_locked())
+}
+
+// Centro de una celda en coordenadas del mundo
+fn center((x, y): Cell) -> Vector2 {
+    Vector2::new(x as f32 + 0.5, y as f32 + 0.5)
+}
+
+// Vecinas a las que se puede pasar desde una celda
+pub fn walkable_neighbors<'a>(
+    maze: &'a Maze,
+    doors: &'a Doors,
+    terrain: &'a Terrain,
+    (x, y): Cell,
+) -> impl Iterator<Item = Cell> + 'a {
+    STEPS.iter().filter_map(move |(dx, dy)| {
+        let (nx, ny) = (x as isize + dx, y as isize + dy);
+        let next = (nx as usize, ny as usize);
+        let step = can_step(maze, &Doors::NONE, terrain, (x as isize, y as isize), (nx, ny));
+        (step && !is_locked(doors, next)).then_some(next)
+    })
+}
+
+// Celda de la grilla que contiene una posicion del mundo
+pub fn cell_of(x: f32, y: f32) -> Option<Cell> {
+    if x < 0.0 || y < 0.0 { return None; }
+    Some((x as usize, y as usize))
+}
+
+// Distancia en pasos de cada celda a la fuente mas cercana (distance_field) o desde
+// ella (reach_field); solo difieren cuando hay escalones que no se pueden subir
+pub struct DistanceField {
+    width: usize,
+    dist: Vec<u32>,
+}
+
+const UNREACHED: u32 = u32::MAX;
+
+impl DistanceField {
+    pub fn get(&self, (x, y): Cell) -> Option<u32> {
+        if x >= self.width { return None; }
+        match self.dist.get(y * self.width + x) {
+            Some(&d) if d != UNREACHED => Some(d),
+            _ => None,
+        }
+    }
+
+    pub fn reaches(&self, cell: Cell) -> bool {
+        self.get(cell).is_some()
+    }
+
+    // Celdas con distancia conocida (las fuentes y todo lo alcanzable desde ellas)
+    pub fn reached(&self) -> impl Iterator<Item = Cell> + '_ {
+        self.dist
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| **d != UNREACHED)
+            .map(|(i, _)| (i % self.width, i / self.width))
+    }
+
+    // Vecina que mas acerca a la fuente (None si ya se esta en una fuente o no se llega).
+    // Los empates se resuelven siempre en el mismo orden de direcciones.
+    pub fn next_step(&self, maze: &Maze, doors: &Doors, terrain: &Terrain, from: Cell) -> Option<Cell> {
+        let d = self.get(from)?;
+        if d == 0 { return None; }
+        STEPS.iter().find_map(|(dx, dy)| {
+            let n = (from.0.checked_add_signed(*dx)?, from.1.checked_add_signed(*dy)?);
+            let is_source = self.get(n) == Some(0);
+            // las fuentes pueden ser paredes
+            let passable = (is_source || cell_is_free(maze, &Doors::NONE, n.0 as isize, n.1 as isize))
+                && !is_locked(doors, n)
+                && !edge_between(maze, from, n)
+                && terrain.can_climb(center(from), center(n));
+            (passable && self.get(n) == Some(d - 1)).then_some(n)
+        })
+    }
+
+    // Camino desde `from` hasta la fuente mas cercana, incluyendo ambos extremos
+    pub fn path_from(&self, maze: &Maze, doors: &Doors, terrain: &Terrain, from: Cell) -> Option<Vec<Cell>> {
+        self.get(from)?;
+        let mut path = vec![from];
+        let mut cell = from;
+        while let Some(next) = self.next_step(maze, doors, terrain, cell) {
+            path.push(next);
+            cell = next;
+        }
+        Some(path)
+    }
+}
+
+// BFS desde varias fuentes a la vez: pasos que faltan desde cada celda para llegar a
+// alguna fuente. Las fuentes no necesitan ser transitables (por ejemplo una pared
+// junto a la que hay que llegar); el resto del recorrido solo pasa por celdas libres.
+pub fn distance_field(maze: &Maze, doors: &Doors, terrain: &Terrain, sources: &[Cell]) -> DistanceField {
+    // al reves que el caminante: desde la vecina hay que poder subir a la celda
+    bfs(maze, doors, sources, |cell, n| terrain.can_climb(center(n), center(cell)))
+}
+
+// Como distance_field pero en el sentido de la marcha: pasos desde la fuente hasta
+// cada celda a la que se puede ir
+pub fn reach_field(maze: &Maze, doors: &Doors, terrain: &Terrain, sources: &[Cell]) -> DistanceField {
+    bfs(maze, doors, sources, |cell, n| terrain.can_climb(center(cell), center(n)))
+}
+
+fn bfs(maze: &Maze, doors: &Doors, sources: &[Cell], climb: impl Fn(Cell, Cell) -> bool) -> DistanceField {
+    let height = maze.len();
+    let width = maze.iter().map(|r| r.len()).max().unwrap_or(0);
+    let mut dist = vec![UNREACHED; width * height];
+    let mut queue = VecDeque::new();
+
+    for &(x, y) in sources {
+        if y < height && x < maze[y].len() && dist[y * width + x] == UNREACHED {
+            dist[y * width + x] = 0;
+            queue.push_back((x, y));
+        }
+    }
+
+    while let Some(cell) = queue.pop_front() {
+        let d = dist[cell.1 * width + cell.0];
+        for (nx, ny) in walkable_neighbors(maze, doors, &Terrain::FLAT, cell) {
+            let i = ny * width + nx;
+            if dist[i] == UNREACHED && climb(cell, (nx, ny)) {
+                dist[i] = d + 1;
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    DistanceField { width, dist }
+}
+
+// Camino mas corto entre dos celdas transitables con A* (heuristica Manhattan).
+// Devuelve las celdas del camino incluyendo inicio y destino.
+pub fn find_path(maze: &Maze, doors: &Doors, terrain: &Terrain, start: Cell, goal: Cell) -> Option<Vec<Cell>> {
+    let free = |(x, y): Cell| cell_is_free(maze, &Doors::NONE, x as isize, y as isize) && !is_locked(doors, (x, y));
+    if !free(start) || !free(goal) { return None; }
+
+    let h = |(x, y): Cell| (x.abs_diff(goal.0) + y.abs_diff(goal.1)) as u32;
+    let mut g_score: HashMap<Cell, u32> = HashMap::from([(start, 0)]);
+    let mut came_from: HashMap<Cell, Cell> = HashMap::new();
+    // cola de prioridad por (f = g + h, g, celda); Reverse la vuelve de minimos
+    let mut open = BinaryHeap::from([Reverse((h(start), 0u32, start))]);
+
+    while let Some(Reverse((_, g, cell))) = open.pop() {
+        if cell == goal {
+            let mut path = vec![goal];
+            let mut c = goal;
+            while let Some(&prev) = came_from.get(&c) {
+                path.push(prev);
+                c = prev;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        if g > g_score[&cell] { continue; }
+
+        for n in walkable_neighbors(maze, doors, terrain, cell) {
+            let tentative = g + 1;
+            if g_score.get(&n).is_none_or(|&old| tentative < old) {
+                g_score.insert(n, tentative);
+                came_from.insert(n, cell);
+                open.push(Reverse((tentative + h(n), tentative, n)));
+            }
+        }
+    }
+    None
+}
+
+// Abre las cerraduras a las que se llega desde `start`: recorre el mapa con todo lo
+// cerrado, saca la cerradura de cada color cuya llave (`keys`) alcanzo y repite hasta
+// que no se abre ninguna mas. Devuelve las puertas como quedaron.
+pub fn unlock_reachable(
+    maze: &Maze,
+    doors: &Doors,
+    terrain: &Terrain,
+    start: Cell,
+    keys: &[(KeyColor, Cell)],
+) -> Doors {
+    let mut doors = doors.clone();
+    loop {
+        let field = reach_field(maze, &doors, terrain, &[start]);
+        let opened: Vec<KeyColor> = keys
+            .iter()
+            .filter(|&&(color, cell)| doors.has_lock(color) && field.reaches(cell))
+            .map(|&(color, _)| color)
+            .collect();
+        if opened.is_empty() { return doors; }
+        opened.into_iter().for_each(|color| doors.unlock(color));
+    }
+}
+
+// Posiciones de las salidas 'g'
+pub fn goal_cells(maze: &Maze) -> Vec<Cell> {
+    maze.iter()
+        .enumerate()
+        .flat_map(|(y, row)| row.iter().enumerate().filter(|(_, c)| **c == 'g').map(move |(x, _)| (x, y)))
+        .collect()
+}
+
+// Guarda los campos de distancia ya calculados por destino. Hay que vaciarlo con
+// `clear` si el laberinto, sus cerraduras o sus alturas cambian.
+#[derive(Default)]
+pub struct PathCache {
+    fields: HashMap<Vec<Cell>, DistanceField>,
+}
+
+impl PathCache {
+    pub fn new() -> Self {
+        PathCache::default()
+    }
+
+    // Campo de distancia hacia un conjunto de celdas
+    pub fn to_cells(&mut self, maze: &Maze, doors: &Doors, terrain: &Terrain, targets: &[Cell]) -> &DistanceField {
+        self.fields
+            .entry(targets.to_vec())
+            .or_insert_with(|| distance_field(maze, doors, terrain, targets))
+    }
+
+    // Campo de distancia hacia la salida (todas las 'g' del mapa)
+    pub fn to_goal(&mut self, maze: &Maze, doors: &Doors, terrain: &Terrain) -> &DistanceField {
+        let goals = goal_cells(maze);
+        self.to_cells(maze, doors, terrain, &goals)
+    }
+
+    pub fn clear(&mut self) {
+        self.fields.clear();
+    }
+}
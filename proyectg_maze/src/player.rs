@@ -1,7 +1,67 @@
 // src/player.rs
 use raylib::prelude::*;
 
+use crate::items::Inventory;
+
 pub struct Player {
     pub pos: Vector2, // definicion de player como vector
     pub a: f32, // angulo de vista
-}
\ No newline at end of file
+    pub inventory: Inventory, // objetos que lleva (huesos, galletas)
+}
+
+impl Player {
+    // Jugador en una posicion, con el inventario vacio
+    pub fn new(pos: Vector2, a: f32) -> Self {
+        Player { pos, a, inventory: Inventory::new() }
+    }
+}
+
+// Radio del jugador para el contacto con los perros (en celdas)
+pub const PLAYER_RADIUS: f32 = 0.25;
+// Segundos sin recibir daño despues de un golpe
+pub const INVULNERABLE_TIME: f32 = 1.0;
+
+// Salud del jugador; las vidas son opcionales (None = una sola oportunidad)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Health {
+    pub hp: f32,
+    pub max: f32,
+    pub lives: Option<u32>,
+    // Tiempo que queda de invulnerabilidad
+    pub invulnerable: f32,
+}
+
+impl Health {
+    pub fn new(max: f32, lives: Option<u32>) -> Self {
+        Health { hp: max, max, lives, invulnerable: 0.0 }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.invulnerable = (self.invulnerable - dt).max(0.0);
+    }
+
+    // Aplica el daño si no esta en el tiempo de invulnerabilidad; true si lo recibio
+    pub fn hit(&mut self, damage: f32) -> bool {
+        if self.invulnerable > 0.0 || self.is_dead() { return false; }
+        self.hp = (self.hp - damage).max(0.0);
+        self.invulnerable = INVULNERABLE_TIME;
+        true
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.hp <= 0.0
+    }
+
+    // Gasta una vida y recupera la salud; false si no quedan vidas
+    pub fn lose_life(&mut self) -> bool {
+        match self.lives {
+            Some(n) if n > 1 => {
+                self.lives = Some(n - 1);
+                self.hp = self.max;
+                self.invulnerable = INVULNERABLE_TIME;
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -1,11 +1,15 @@
 // src/render3d.rs
-use raylib::color::Color;
+use raylib::prelude::*;
+use std::thread;
 
-use crate::framebuffer::{Framebuffer, symbol_to_color};
-use crate::player::Player;
-use crate::caster::cast_ray;
+use crate::framebuffer::{Framebuffer, blend};
+use crate::camera::Camera;
+use crate::caster::{Intersect, cast_ray_layers};
+use crate::doors::Doors;
 use crate::maze::Maze;
+use crate::terrain::Terrain;
 use crate::textures::TextureManager;
+use crate::walls::is_see_through;
 
 // Funcion para crear efecto de vista nublada a lo lejos
 fn shade(color: Color, factor: f32) -> Color {
@@ -18,130 +22,284 @@ fn shade(color: Color, factor: f32) -> Color {
     }
 }
 
+// Hilos disponibles en la maquina (1 si no se puede consultar)
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+// Tramo vertical de pared dibujado en una columna
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Slice {
+    // Profundidad perpendicular al plano de camara
+    pub depth: f32,
+    // Fila de arriba (puede quedar fuera de la pantalla) y pixeles por bloque de altura
+    pub start: f32,
+    pub scale: f32,
+    // Filas [top, bottom) que ocupa dentro de la pantalla
+    pub top: usize,
+    pub bottom: usize,
+    pub impact: char,
+    // Columna de la textura
+    pub tx: u32,
+    pub shade: f32,
+}
+
+impl Slice {
+    fn new(intersect: &Intersect, camera: &Camera, tex: &TextureManager) -> Self {
+        // distancia perpendicular al plano de camara (sin efecto ojo de pez)
+        let hit = Vector2::new(intersect.hit_x, intersect.hit_y);
+        let depth = camera.depth(hit).max(camera.near);
+
+        // Tamaño de las estacas: van del piso base hasta la altura de lo golpeado,
+        // vistas desde la altura de los ojos
+        let (h, hh) = (camera.height as f32, camera.horizon());
+        let scale = camera.projection_distance() / depth;
+        let start = hh + (camera.eye() - intersect.height) * scale;
+        let top = start.clamp(0.0, h) as usize;
+        let bottom = (hh + camera.eye() * scale).clamp(0.0, h) as usize;
+
+        // u en [0..1] segun la cara (el caster ya lo calcula para las paredes finas,
+        // los pilares y la hoja corrida de las puertas)
+        let (tw, _) = tex.get_image_size(intersect.impact);
+        let u = intersect.tex_u.clamp(0.0, 1.0);
+        let tx = (u * tw as f32).clamp(0.0, (tw - 1) as f32) as u32;
+
+        // la niebla depende de la distancia real recorrida por el rayo
+        let shade = (1.0 / (1.0 + 0.4 * intersect.distance)).clamp(0.5, 1.0);
+
+        Slice { depth, start, scale, top, bottom, impact: intersect.impact, tx, shade }
+    }
+
+    // Color de la textura en la fila y (None fuera del tramo). La textura se repite
+    // una vez por bloque de altura, desde arriba
+    pub fn texel(&self, tex: &TextureManager, y: usize) -> Option<Color> {
+        if y < self.top || y >= self.bottom { return None; }
+        let (_, th) = tex.get_image_size(self.impact);
+        let rel = ((y as f32) - self.start) / self.scale.max(1.0);
+        let ty = (rel.fract() * th as f32).clamp(0.0, (th - 1) as f32) as u32;
+        Some(shade(tex.get_pixel_color(self.impact, self.tx, ty), self.shade))
+    }
+}
+
+// Parte opaca de una columna: de la fila `top` para abajo todo esta tapado por algo
+// que esta a `depth` o mas cerca
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cover {
+    pub depth: f32,
+    pub top: usize,
+}
+
+// Profundidad de cada columna para ocultar sprites: lo opaco, de adelante hacia atras
+// (cada tramo mas lejos y mas arriba que el anterior), y los tramos de paredes
+// transparentes (de atras hacia adelante), que se vuelven a componer sobre los
+// sprites que quedan detras de ellos
+pub struct ZBuffer {
+    pub covers: Vec<Vec<Cover>>,
+    pub layers: Vec<Vec<Slice>>,
+}
+
+impl ZBuffer {
+    // Filas de la columna `col`, desde arriba, que no tapa nada que este a `depth` o
+    // mas cerca
+    pub fn visible_rows(&self, col: usize, depth: f32) -> usize {
+        self.covers
+            .get(col)
+            .and_then(|cs| cs.iter().take_while(|c| c.depth <= depth).last())
+            .map_or(usize::MAX, |c| c.top)
+    }
+}
+
+// Renderiza paredes, piso y cielo. Las columnas se reparten en franjas entre
+// `threads` hilos; con threads = 1 todo corre en el hilo actual. El resultado
+// es identico en ambos casos porque cada columna es independiente.
 pub fn render3d(
-    framebuffer: &mut Framebuffer, 
-    maze: &Maze, 
-    player: &Player, 
-    block_size: usize, 
-    offset_x: i32, 
-    offset_y: i32,
-    tex: &TextureManager
-) -> Vec<f32> {
-    // ancho y alto
-    let w = framebuffer.width() as f32;
-    let h = framebuffer.height() as f32;
-
-    // medio ancho y medio alto
-    let hw = w / 2.0;
-    let hh = h / 2.0;
-
-    let num_rays = framebuffer.width(); // numero de rayos = ancho del framebuffer
-    let mut zbuffer = vec![f32::INFINITY; num_rays as usize];
+    framebuffer: &mut Framebuffer,
+    maze: &Maze,
+    doors: &Doors,
+    terrain: &Terrain,
+    camera: &Camera,
+    tex: &TextureManager,
+    threads: usize,
+) -> ZBuffer {
+    debug_assert_eq!((camera.width, camera.height), (framebuffer.width(), framebuffer.height()));
+
+    let num_rays = camera.width as usize; // numero de rayos = ancho de la camara
+    let h = camera.height as usize;
+    let mut covers = vec![Vec::new(); num_rays];
+    let mut layers = vec![Vec::new(); num_rays];
+
+    // Cada franja se guarda por columnas (h pixeles seguidos por columna)
+    let mut columns = vec![Color::BLACK; num_rays * h];
+
+    let threads = threads.clamp(1, num_rays.max(1));
+    if threads == 1 {
+        render_strip(&mut columns, &mut covers, &mut layers, 0, maze, doors, terrain, camera, tex);
+    } else {
+        let strip = num_rays.div_ceil(threads);
+        thread::scope(|s| {
+            for (n, ((cols, cs), ls)) in columns
+                .chunks_mut(strip * h)
+                .zip(covers.chunks_mut(strip))
+                .zip(layers.chunks_mut(strip))
+                .enumerate()
+            {
+                s.spawn(move || render_strip(cols, cs, ls, n * strip, maze, doors, terrain, camera, tex));
+            }
+        });
+    }
+
+    framebuffer.write_columns(0, &columns);
+
+    ZBuffer { covers, layers }
+}
+
+// Distancia perpendicular (en multiplos de `ray`) a la que el rayo sale de la grilla
+fn map_exit(maze: &Maze, pos: Vector2, ray: Vector2) -> f32 {
+    let width = maze.iter().map(|r| r.len()).max().unwrap_or(0) as f32;
+    let height = maze.len() as f32;
+    let axis = |p: f32, d: f32, size: f32| {
+        if d > 0.0 { (size - p) / d } else if d < 0.0 { -p / d } else { f32::INFINITY }
+    };
+    axis(pos.x, ray.x, width).min(axis(pos.y, ray.y, height))
+}
+
+// Renderiza las columnas [first, first + covers.len()) en `columns`
+#[allow(clippy::too_many_arguments)]
+fn render_strip(
+    columns: &mut [Color],
+    covers: &mut [Vec<Cover>],
+    layers: &mut [Vec<Slice>],
+    first: usize,
+    maze: &Maze,
+    doors: &Doors,
+    terrain: &Terrain,
+    camera: &Camera,
+    tex: &TextureManager,
+) {
+    let h = camera.height as usize;
+    for (k, ((col, cs), ls)) in columns.chunks_mut(h).zip(covers.iter_mut()).zip(layers.iter_mut()).enumerate() {
+        (*cs, *ls) = render_column(col, (first + k) as u32, maze, doors, terrain, camera, tex);
+    }
+}
+
+// Dibuja una columna de la vista y devuelve lo opaco para el z-buffer junto con los
+// tramos transparentes que quedaron delante
+fn render_column(
+    col: &mut [Color],
+    i: u32,
+    maze: &Maze,
+    doors: &Doors,
+    terrain: &Terrain,
+    camera: &Camera,
+    tex: &TextureManager,
+) -> (Vec<Cover>, Vec<Slice>) {
+    // linea del horizonte (se desplaza con la inclinacion de la camara)
+    let hh = camera.horizon();
+    let eye = camera.eye();
 
     // Colores base para el cielo y el suelo
     let sky_base = Color::SKYBLUE;
     let floor_char = ' ';
     let (tw, th) = tex.get_image_size(floor_char);
 
-    // angulo de visión horizontal
-    let fov = 1.047; // -60 grados
-    let b = block_size as f32;
-
-    // posicion del jugador en pixeles
-    let start_x = offset_x as f32 + player.pos.x * b;
-    let start_y = offset_y as f32 + player.pos.y * b;
-
-    for i in 0..num_rays {
-        let current_ray = i as f32 / num_rays as f32; // rayo actual dividido en rayos totales
-        let a = player.a - (fov / 2.0) + (fov * current_ray);
-
-        if let Some(intersect) = cast_ray(
-            framebuffer, &maze, player, a, block_size, offset_x, offset_y, false
-        ) {
-            let distance = intersect.distance.max(1.0);
-            zbuffer[i as usize] = distance;
-           
-            // Tamaño de las estacas
-            let dpp = (w / 2.0) / (fov / 2.0).tan();
-            let stake_height = (block_size as f32 / distance) * dpp;
-
-            let stake_top = (hh - (stake_height / 2.0).max(0.0)) as usize;
-            let stake_bottom = (hh + (stake_height / 2.0).min(h)) as usize;
-
-            // color del cielo
-            for y in 0..stake_top {
-                framebuffer.set_pixel_i32(i as i32, y as i32, sky_base);
-            }
-            
-            // Textura de paredes
-            let wall_ch = intersect.impact;
-            let (tw_wall, th_wall) = tex.get_image_size(wall_ch);
-
-            // Coordenadas locales dentro del tile donde impactó
-            let local_x = (intersect.hit_x - offset_x as f32).rem_euclid(b);
-            let local_y = (intersect.hit_y - offset_y as f32).rem_euclid(b);
-
-            // u en [0..1] segun cara
-            let mut u = if intersect.vertical { local_y / b } else { local_x / b };
-            // Voltear para mantener orientación consistente
-            if intersect.vertical {
-                if a.cos() < 0.0 { u = 1.0 - u; }
-            } else {
-                if a.sin() > 0.0 { u = 1.0 - u; }
-            }
-            let tx = (u * tw_wall as f32).clamp(0.0, (tw_wall - 1) as f32) as u32;
-
-            let shade_factor = (1.0 / (1.0 + 0.007 * distance)).clamp(0.5, 1.0);
+    // distancia al plano de proyeccion segun el FOV de la camara
+    let dpp = camera.projection_distance();
 
-            for y in stake_top..stake_bottom {
-                let rel = ((y as f32) - (stake_top as f32)) / stake_height.max(1.0);
-                let ty = (rel * th_wall as f32).clamp(0.0, (th_wall - 1) as f32) as u32;
+    // rayo que pasa por la columna i del plano de camara
+    let ray = camera.ray_dir(i);
 
-                let texel = tex.get_pixel_color(wall_ch, tx, ty);
-                let color = shade(texel, shade_factor);
+    // paredes y pisos elevados de adelante hacia atras; al final, la pared opaca que
+    // tapa todo lo de atras (si el rayo no sale del mapa)
+    let hits = cast_ray_layers(maze, doors, terrain, camera.pos, ray);
 
-                framebuffer.set_pixel_i32(i as i32, y as i32, color);
-            }
+    // Fondo: cielo y suelo a la altura 0. Si el rayo no golpea nada opaco sale del
+    // mapa; el suelo que queda mas alla del borde es de un solo color
+    let open = hits.iter().all(|hit| is_see_through(hit.impact));
+    let edge = if open { map_exit(maze, camera.pos, ray) } else { f32::INFINITY };
+    for (y, px) in col.iter_mut().enumerate() {
+        let yf = y as f32;
 
-            // Textura del suelo
-            for y in stake_bottom..(h as usize) {
-                let yf = y as f32;
+        // Evitar división por 0 cerca del horizonte
+        if yf <= hh + 0.5 {
+            *px = sky_base;
+            continue;
+        }
+        // Distancia perpendicular al punto del piso
+        let dist_y = dpp / ((yf - hh) / eye);
+        if dist_y > edge {
+            *px = tex.get_pixel_color(floor_char, 0, 0);
+            continue;
+        }
 
-                // Evitar división por 0 cerca del horizonte
-                if yf <= hh + 0.5 { 
-                    framebuffer.set_pixel_i32(i as i32, y as i32, sky_base);
-                    continue;
-                }
-                // Distancia aproximada al punto del piso
-                let dist_y = (b * dpp) / (2.0 * (yf - hh));
+        // Punto del mundo a esa distancia sobre el rayo de este píxel
+        let fx = camera.pos.x + dist_y * ray.x;
+        let fy = camera.pos.y + dist_y * ray.y;
 
-                // Punto del mundo a esa distancia sobre el rayo de este píxel
-                let fx = start_x + dist_y * a.cos();
-                let fy = start_y + dist_y * a.sin();
+        // Coordenada local dentro de la celda (0..1)
+        let local_x = fx.rem_euclid(1.0);
+        let local_y = fy.rem_euclid(1.0);
 
-                // Coordenada local dentro del tile (0..block_size)
-                let local_x = (fx - offset_x as f32).rem_euclid(b);
-                let local_y = (fy - offset_y as f32).rem_euclid(b);
+        // Llevar a coordenadas de textura (0..tw/th)
+        let tx = (local_x * tw as f32) as u32;
+        let ty = (local_y * th as f32) as u32;
 
-                // Llevar a coordenadas de textura (0..tw/th)
-                let tx = ((local_x / b) * tw as f32) as u32;
-                let ty = ((local_y / b) * th as f32) as u32;
+        // Muestrear textura y aplicar un sombreado suave por profundidad visual
+        *px = tex.get_pixel_color(floor_char, tx, ty);
+    }
 
-                // Muestrear textura y aplicar un sombreado suave por profundidad visual
-                let floor_color = tex.get_pixel_color(floor_char, tx, ty);
+    // Lo opaco se pinta de adelante hacia atras: las filas desde `clip` para abajo ya
+    // las tapa algo mas cercano. Lo transparente se guarda para mezclarlo al final
+    let mut clip = col.len();
+    let mut covers = Vec::new();
+    let mut slices = Vec::new();
+    for hit in &hits {
+        let mut face = Slice::new(hit, camera, tex);
+        face.bottom = face.bottom.min(clip);
+        if is_see_through(hit.impact) {
+            slices.push(face);
+            continue;
+        }
 
-                framebuffer.set_pixel_i32(i as i32, y as i32, floor_color);
+        // Textura de paredes (o el costado de un piso elevado)
+        for (y, px) in col.iter_mut().enumerate().take(face.bottom).skip(face.top) {
+            if let Some(texel) = face.texel(tex, y) {
+                *px = texel;
             }
+        }
+        if face.top < clip {
+            covers.push(Cover { depth: face.depth, top: face.top });
+            clip = face.top;
+        }
+
+        // Cara de arriba, visible si los ojos estan mas altos: va desde la cara golpeada
+        // hasta donde el rayo sale de la celda
+        if eye <= hit.height || hit.exit <= hit.distance { continue; }
+        let far = face.depth + (hit.exit - hit.distance) / ray.length();
+        let rise = (eye - hit.height) * dpp;
+        let from = (hh + rise / far).clamp(0.0, clip as f32) as usize;
+        let (sw, sh) = tex.get_image_size(hit.impact);
+        for (y, px) in col.iter_mut().enumerate().take(clip).skip(from) {
+            let d = rise / (y as f32 - hh).max(0.5);
+            let (fx, fy) = (camera.pos.x + d * ray.x, camera.pos.y + d * ray.y);
+            let tx = (fx.rem_euclid(1.0) * sw as f32) as u32;
+            let ty = (fy.rem_euclid(1.0) * sh as f32) as u32;
+            *px = tex.get_pixel_color(hit.impact, tx, ty);
+        }
+        if from < clip {
+            covers.push(Cover { depth: far, top: from });
+            clip = from;
+        }
+    }
 
-        } else { // Si el rayo no golpea nada, la region se mantiene negra
-            for y in 0..(h as usize) {
-                let color = if (y as f32) < hh { sky_base } else {
-                    tex.get_pixel_color(floor_char, 0, 0)
-                    };
-                framebuffer.set_pixel_i32(i as i32, y as i32, color);
+    // Paredes transparentes de atras hacia adelante, mezcladas segun el alfa
+    slices.reverse();
+    for slice in &slices {
+        for (y, px) in col.iter_mut().enumerate().take(slice.bottom).skip(slice.top) {
+            if let Some(texel) = slice.texel(tex, y) {
+                *px = blend(*px, texel);
             }
         }
     }
 
-    return zbuffer;
-}
\ No newline at end of file
+    (covers, slices)
+}
@@ -0,0 +1,103 @@
+// src/resolution.rs
+
+use crate::camera::Camera;
+use crate::framebuffer::Framebuffer;
+
+// Resolucion interna con la que se renderiza la vista 3D antes de escalarla a la ventana
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenderResolution {
+    // Tamaño fijo en pixeles, por ejemplo 320x200
+    Fixed(u32, u32),
+    // Fraccion del tamaño de la ventana, por ejemplo 0.5
+    Scale(f32),
+}
+
+// Limites del factor que aplica el modo dinamico sobre la resolucion elegida
+const MIN_DYNAMIC_SCALE: f32 = 0.25;
+const MAX_DYNAMIC_SCALE: f32 = 1.0;
+
+// Tiempo minimo entre ajustes del modo dinamico (segundos)
+const ADJUST_INTERVAL: f32 = 0.5;
+
+pub struct ResolutionScaler {
+    pub mode: RenderResolution,
+    // Si es Some, la resolucion baja cuando el costo del frame supera este presupuesto (segundos)
+    pub budget: Option<f32>,
+    dynamic_scale: f32,
+    avg_cost: f32,
+    since_adjust: f32,
+}
+
+impl ResolutionScaler {
+    pub fn new(mode: RenderResolution) -> Self {
+        ResolutionScaler {
+            mode,
+            budget: None,
+            dynamic_scale: MAX_DYNAMIC_SCALE,
+            avg_cost: 0.0,
+            since_adjust: 0.0,
+        }
+    }
+
+    // Activa el modo dinamico con un presupuesto por frame en segundos
+    pub fn with_dynamic(mut self, budget: f32) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    // Tamaño interno para una ventana de win_w x win_h (nunca menor a 1x1)
+    pub fn target_size(&self, win_w: u32, win_h: u32) -> (u32, u32) {
+        let (w, h) = match self.mode {
+            RenderResolution::Fixed(w, h) => (w as f32, h as f32),
+            RenderResolution::Scale(s) => (win_w as f32 * s, win_h as f32 * s),
+        };
+        let k = if self.budget.is_some() { self.dynamic_scale } else { 1.0 };
+        (((w * k) as u32).max(1), ((h * k) as u32).max(1))
+    }
+
+    // Registra el costo de render del ultimo frame y ajusta el factor dinamico
+    pub fn update(&mut self, cost: f32, dt: f32) {
+        let Some(budget) = self.budget else { return; };
+
+        // promedio movil para no reaccionar a picos aislados
+        self.avg_cost = if self.avg_cost == 0.0 { cost } else { self.avg_cost * 0.9 + cost * 0.1 };
+        self.since_adjust += dt;
+        if self.since_adjust < ADJUST_INTERVAL {
+            return;
+        }
+
+        if self.avg_cost > budget * 1.1 {
+            self.dynamic_scale = (self.dynamic_scale * 0.85).max(MIN_DYNAMIC_SCALE);
+            self.since_adjust = 0.0;
+        } else if self.avg_cost < budget * 0.6 {
+            self.dynamic_scale = (self.dynamic_scale * 1.1).min(MAX_DYNAMIC_SCALE);
+            self.since_adjust = 0.0;
+        }
+    }
+
+    // Ajusta framebuffer y camara al tamaño objetivo; devuelve true si cambio
+    pub fn apply(&self, framebuffer: &mut Framebuffer, camera: &mut Camera, win_w: u32, win_h: u32) -> bool {
+        let (w, h) = self.target_size(win_w, win_h);
+        if framebuffer.width() == w && framebuffer.height() == h {
+            return false;
+        }
+        framebuffer.resize(w, h);
+        camera.set_resolution(w, h);
+        true
+    }
+}
+
+// Interpreta "320x200" como resolucion fija o "0.5" / "50%" como escala de la ventana
+pub fn parse_resolution(s: &str) -> Option<RenderResolution> {
+    if let Some((w, h)) = s.split_once('x') {
+        let w = w.trim().parse::<u32>().ok()?;
+        let h = h.trim().parse::<u32>().ok()?;
+        if w == 0 || h == 0 { return None; }
+        return Some(RenderResolution::Fixed(w, h));
+    }
+    let scale = match s.strip_suffix('%') {
+        Some(p) => p.trim().parse::<f32>().ok()? / 100.0,
+        None => s.trim().parse::<f32>().ok()?,
+    };
+    if scale > 0.0 && scale <= 4.0 { Some(RenderResolution::Scale(scale)) } else { None }
+}
@@ -1,14 +1,13 @@
 // src/sprites.rs
-use std::f32::consts::{PI};
 use raylib::prelude::*;
 use raylib::color::Color;
 
-use crate::player::Player;
+use crate::camera::Camera;
 use crate::framebuffer::Framebuffer;
+use crate::render3d::{Slice, ZBuffer};
+use crate::terrain::Terrain;
 use crate::textures::TextureManager;
 
-const FOV: f32 = 1.047; // (60)
-
 pub struct Sprite {
     pub pos: Vector2,
     pub tex_keys: Vec<char>,
@@ -32,6 +31,20 @@ impl Sprite {
     }
 }
 
+// Sprites iniciales del nivel, ubicados respecto al spawn
+pub fn default_sprites(spawn_x: usize, spawn_y: usize) -> Vec<Sprite> {
+    vec![
+        Sprite {
+            pos: Vector2::new(spawn_x as f32 + 3.5, spawn_y as f32 + 1.5),
+            tex_keys: vec!['c', 'a'],
+            current_frame: 0,
+            frame_time: 0.1,
+            timer: 0.0,
+            size: 1.0,
+        }
+    ]
+}
+
 fn shade(color: Color, factor: f32) -> Color {
     let f = factor.clamp(0.0, 1.0);
     Color {
@@ -42,69 +55,56 @@ fn shade(color: Color, factor: f32) -> Color {
     }
 }
 
-fn normalize_angle(mut a: f32) -> f32 {
-    let two_pi: f32 = 2.0_f32 * PI;
-    while a >  PI { a -= two_pi; }
-    while a < -PI { a += two_pi; }
-    a
-}
-
 /// Dibuja todos los sprites con recorte por FOV y oclusión usando z-buffer.
-pub fn draw_sprites(
+/// Acepta cualquier coleccion de sprites (por ejemplo los del nivel mas los objetos).
+/// Los pixeles transparentes no se dibujan y las paredes transparentes que quedan
+/// delante del sprite se vuelven a mezclar encima. Cada sprite se apoya en el piso
+/// de su celda (terrain.rs).
+pub fn draw_sprites<'a>(
     framebuffer: &mut Framebuffer,
-    player: &Player,
-    sprites: &[Sprite],
-    block_size: usize,
-    _offset_x: i32,
-    _offset_y: i32,
+    camera: &Camera,
+    terrain: &Terrain,
+    sprites: impl IntoIterator<Item = &'a Sprite>,
     tex: &TextureManager,
-    zbuffer: &[f32],
+    zbuffer: &ZBuffer,
 ) {
-    let w = framebuffer.width() as f32;
-    let h = framebuffer.height() as f32;
+    let w = camera.width as f32;
+    let h = camera.height as f32;
     let hw = w * 0.5;
-    let hh = h * 0.5;
+    let hh = camera.horizon();
 
     // plano de proyección (mismo que en render3d)
-    let dpp = (w * 0.5) / (FOV * 0.5).tan();
-    let b = block_size as f32;
-
-    // Ordena por distancia descendente (pintar de lejos a cerca)
-    let mut order: Vec<(usize, f32)> = sprites.iter().enumerate().map(|(i, s)| {
-        let dxp = (s.pos.x - player.pos.x) * b;
-        let dyp = (s.pos.y - player.pos.y) * b;
-        (i, (dxp*dxp + dyp*dyp).sqrt())
-    }).collect();
-    order.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
-
-    for (idx, dist) in order {
-        let spr = &sprites[idx];
-
-        // vector al sprite (en píxeles del mundo)
-        let dxp = (spr.pos.x - player.pos.x) * b;
-        let dyp = (spr.pos.y - player.pos.y) * b;
-
-        // ángulo relativo al jugador
-        let ang_to_sprite = dyp.atan2(dxp);
-        let mut ang = normalize_angle(ang_to_sprite - player.a);
-
-        let extra_margin = 0.1;
-        if ang.abs() > (FOV * 0.5 + extra_margin) {
+    let dpp = camera.projection_distance();
+
+    // Ordena por profundidad descendente (pintar de lejos a cerca)
+    let mut order: Vec<(&Sprite, Vector2)> = sprites.into_iter()
+        .map(|s| (s, camera.to_camera_space(s.pos)))
+        .collect();
+    order.sort_by(|a, b| b.1.y.partial_cmp(&a.1.y).unwrap());
+
+    for (spr, cam) in order {
+
+        // profundidad perpendicular al plano de camara, igual que el z-buffer
+        let dist = cam.y;
+        if dist <= camera.near {
             continue;
         }
 
         // posición horizontal en la pantalla (centro del sprite)
-        let screen_x = hw * (1.0 + ang / (FOV * 0.5));
+        let screen_x = hw * (1.0 + cam.x / dist);
 
         // tamaño proyectado
-        let size_px = ((block_size as f32) / dist.max(1.0)) * dpp * spr.size;
+        let size_px = (1.0 / dist) * dpp * spr.size;
         let half = size_px * 0.5;
 
+        // centro vertical: medio bloque por encima del piso de su celda
+        let cy = hh + (camera.eye() - terrain.floor_at(spr.pos) - 0.5) * dpp / dist;
+
         // caja en pantalla
         let mut start_x = (screen_x - half).floor() as i32;
         let mut end_x   = (screen_x + half).ceil() as i32;
-        let mut start_y = (hh - half).floor() as i32;
-        let mut end_y   = (hh + half).ceil() as i32;
+        let mut start_y = (cy - half).floor() as i32;
+        let mut end_y   = (cy + half).ceil() as i32;
 
         // recorte a la pantalla
         if end_x < 0 || start_x >= w as i32 { continue; }
@@ -115,26 +115,40 @@ pub fn draw_sprites(
         end_y   = end_y.min(h as i32 - 1);
 
         let (tw, th) = tex.get_image_size(spr.current_tex_key());
-        let shade_factor = (1.0 / (1.0 + 0.007 * dist)).clamp(0.5, 1.0);
+        let shade_factor = (1.0 / (1.0 + 0.4 * dist)).clamp(0.5, 1.0);
 
         // barrido por columnas con test de profundidad por zbuffer
         for x in start_x..=end_x {
             let col = x as usize;
-            if col < zbuffer.len() && dist >= zbuffer[col] {
+            // filas de arriba que no tapa nada mas cercano que el sprite
+            let visible = zbuffer.visible_rows(col, dist);
+            if visible == 0 {
                 continue;
             }
+            // tramos transparentes de esta columna que tapan al sprite
+            let in_front: &[Slice] = zbuffer
+                .layers
+                .get(col)
+                .map_or(&[], |ls| &ls[ls.iter().position(|l| l.depth < dist).unwrap_or(ls.len())..]);
 
             let u = ((x as f32 - (screen_x - half)) / size_px).clamp(0.0, 1.0);
             let tx = (u * (tw - 1) as f32) as u32;
 
             for y in start_y..=end_y {
-                let v = ((y as f32 - (hh - half)) / size_px).clamp(0.0, 1.0);
+                if y as usize >= visible { break; }
+                let v = ((y as f32 - (cy - half)) / size_px).clamp(0.0, 1.0);
                 let ty = (v * (th - 1) as f32) as u32;
 
                 let px = tex.get_pixel_color(spr.current_tex_key(), tx, ty);
+                if px.a == 0 { continue; }
 
                 let c = shade(px, shade_factor);
-                framebuffer.set_pixel_i32(x, y, c);
+                framebuffer.blend_pixel_i32(x, y, c);
+                for slice in in_front {
+                    if let Some(texel) = slice.texel(tex, y as usize) {
+                        framebuffer.blend_pixel_i32(x, y, texel);
+                    }
+                }
             }
         }
     }
@@ -0,0 +1,34 @@
+// src/stats.rs
+use raylib::prelude::*;
+
+// Estadisticas de una partida en un nivel, para la pantalla de nivel completado
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LevelStats {
+    // Segundos jugados (sin contar la pausa)
+    pub elapsed: f32,
+    // Cantidad de celdas nuevas a las que entro el jugador
+    pub steps: u32,
+    // Distancia recorrida en celdas
+    pub distance: f32,
+}
+
+impl LevelStats {
+    pub fn new() -> Self {
+        LevelStats::default()
+    }
+
+    // Registra un frame de juego en el que el jugador paso de `from` a `to`
+    pub fn record(&mut self, from: Vector2, to: Vector2, dt: f32) {
+        self.elapsed += dt;
+        self.distance += (to - from).length();
+        if (from.x.floor(), from.y.floor()) != (to.x.floor(), to.y.floor()) {
+            self.steps += 1;
+        }
+    }
+}
+
+// Tiempo como m:ss.d
+pub fn format_time(secs: f32) -> String {
+    let tenths = (secs.max(0.0) * 10.0).round() as u32;
+    format!("{}:{:02}.{}", tenths / 600, (tenths / 10) % 60, tenths % 10)
+}
@@ -0,0 +1,116 @@
+// src/terrain.rs
+// Alturas por celda. Las paredes pueden ser mas bajas que un bloque (medias paredes,
+// setos por encima de los que se ve) o mas altas (torres), y el piso de las celdas
+// libres puede estar elevado (escalones, plataformas). Se suben escalones de hasta
+// MAX_STEP y se baja de cualquier altura (controller::can_step). Las paredes siempre
+// arrancan en el piso base (altura 0).
+//
+// En los niveles las alturas se dan en una grilla aparte (`heights`) alineada con
+// `grid`: cada digito es un multiplo de HEIGHT_STEP y ' ' o '.' dejan el valor por
+// defecto. En una pared es su altura; en una celda libre, la del piso.
+
+use raylib::prelude::Vector2;
+use std::collections::BTreeMap;
+
+use crate::camera::EYE_HEIGHT;
+use crate::doors::is_door;
+use crate::maze::{Maze, is_wall};
+use crate::pathfinding::Cell;
+use crate::walls::{edge_face, is_thin};
+
+// Altura de una pared comun (en bloques)
+pub const WALL_HEIGHT: f32 = 1.0;
+// Altura que vale cada digito de la grilla `heights`
+pub const HEIGHT_STEP: f32 = 0.25;
+// Desnivel maximo que el jugador sube caminando
+pub const MAX_STEP: f32 = 0.5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Terrain {
+    walls: BTreeMap<Cell, f32>,
+    floors: BTreeMap<Cell, f32>,
+    // Una pared opaca de al menos esta altura tapa todo lo que hay detras, mire
+    // desde donde mire el jugador
+    top: f32,
+}
+
+impl Default for Terrain {
+    fn default() -> Self {
+        Terrain::FLAT
+    }
+}
+
+impl Terrain {
+    // Todas las paredes de un bloque y el piso plano
+    pub const FLAT: Terrain = Terrain { walls: BTreeMap::new(), floors: BTreeMap::new(), top: WALL_HEIGHT };
+
+    // Alturas a partir de la grilla `heights` de un nivel (ver arriba)
+    pub fn from_grid(maze: &Maze, text: &str) -> Result<Terrain, String> {
+        let mut terrain = Terrain::FLAT;
+        for (y, line) in text.lines().map(|l| l.trim_end_matches('\r')).enumerate() {
+            for (x, h) in line.chars().enumerate() {
+                if h == ' ' || h == '.' { continue; }
+                let Some(&c) = maze.get(y).and_then(|row| row.get(x)) else {
+                    return Err(format!("la altura en ({}, {}) esta fuera de la grilla", x, y));
+                };
+                let Some(n) = h.to_digit(10) else {
+                    return Err(format!("altura invalida '{}' en ({}, {})", h, x, y));
+                };
+                let height = n as f32 * HEIGHT_STEP;
+                if is_door(c) || edge_face(c).is_some() {
+                    return Err(format!("la celda '{}' en ({}, {}) no admite altura", c, x, y));
+                }
+                if is_wall(c) {
+                    if n == 0 {
+                        return Err(format!("la pared en ({}, {}) necesita una altura mayor que 0", x, y));
+                    }
+                    terrain.set_wall_height((x, y), height);
+                } else {
+                    terrain.set_floor_height((x, y), height);
+                }
+            }
+        }
+        Ok(terrain)
+    }
+
+    pub fn set_wall_height(&mut self, cell: Cell, height: f32) {
+        self.walls.insert(cell, height);
+        self.top = self.top.max(height);
+    }
+
+    pub fn set_floor_height(&mut self, cell: Cell, height: f32) {
+        self.floors.insert(cell, height);
+        // parado en la plataforma se ve por encima de las paredes mas bajas que el ojo
+        self.top = self.top.max(height + EYE_HEIGHT);
+    }
+
+    pub fn wall_height(&self, cell: Cell) -> f32 {
+        self.walls.get(&cell).copied().unwrap_or(WALL_HEIGHT)
+    }
+
+    pub fn floor_height(&self, cell: Cell) -> f32 {
+        self.floors.get(&cell).copied().unwrap_or(0.0)
+    }
+
+    // Altura de lo que ocupa la celda `cell` con simbolo `c`: la pared si la hay
+    // (tambien las finas) o el piso
+    pub fn height(&self, cell: Cell, c: char) -> f32 {
+        if is_wall(c) || is_thin(c) { self.wall_height(cell) } else { self.floor_height(cell) }
+    }
+
+    // Altura del piso en una posicion del mundo
+    pub fn floor_at(&self, pos: Vector2) -> f32 {
+        if pos.x < 0.0 || pos.y < 0.0 { return 0.0; }
+        self.floor_height((pos.x as usize, pos.y as usize))
+    }
+
+    // true si se puede caminar de `from` a `to`: subiendo a lo sumo MAX_STEP
+    pub fn can_climb(&self, from: Vector2, to: Vector2) -> bool {
+        self.floor_at(to) - self.floor_at(from) <= MAX_STEP
+    }
+
+    // true si una pared opaca de esa altura tapa todo lo que hay detras
+    pub fn hides_behind(&self, height: f32) -> bool {
+        height >= self.top
+    }
+}
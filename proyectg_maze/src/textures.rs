@@ -1,48 +1,116 @@
 // src/textures.rs
 use raylib::prelude::*;
 use std::collections::HashMap;
-use std::slice;
+
+use crate::framebuffer::symbol_to_color;
+
+// Pixeles de una imagen ya convertidos a RGBA en memoria; se pueden leer desde
+// varios hilos sin tocar raylib
+struct TexImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<Color>,
+}
+
+impl TexImage {
+    fn from_image(image: &Image) -> Self {
+        TexImage {
+            width: image.width as u32,
+            height: image.height as u32,
+            // get_image_data convierte cualquier formato de pixel a RGBA8
+            pixels: image.get_image_data().to_vec(),
+        }
+    }
+}
 
 pub struct TextureManager {
-    images: HashMap<char, Image>,
+    images: HashMap<char, TexImage>,
     textures: HashMap<char, Texture2D>,
 }
 
+// Mapeo de caracteres en assets (el que usa un nivel si no define el suyo)
+pub const TEXTURE_FILES: [(char, &str); 27] = [
+    ('+', "assets/wall1.png"),
+    ('-', "assets/wall2.png"),
+    ('|', "assets/wall3.png"),
+    ('g', "assets/wall4.png"),
+    (' ', "assets/floor.png"),
+    ('c', "assets/hungrycorgi.png"),
+    ('a', "assets/angricorgi.png"),
+    ('b', "assets/hungrybullterrier.png"),
+    ('m', "assets/hungrymastif.png"),
+    ('o', "assets/bone.png"),
+    ('t', "assets/treat.png"),
+    ('D', "assets/door.png"),
+    ('R', "assets/door_red.png"),
+    ('B', "assets/door_blue.png"),
+    ('Y', "assets/door_yellow.png"),
+    ('r', "assets/key_red.png"),
+    ('u', "assets/key_blue.png"),
+    ('y', "assets/key_yellow.png"),
+    ('_', "assets/wall2.png"),
+    ('~', "assets/wall2.png"),
+    ('[', "assets/wall2.png"),
+    (']', "assets/wall2.png"),
+    ('F', "assets/fence.png"),
+    ('W', "assets/window.png"),
+    ('I', "assets/pillar.png"),
+    ('#', "assets/bars.png"),
+    ('H', "assets/hedge.png"),
+];
+
+// Mapeo por defecto como lista editable (los niveles lo sobreescriben por simbolo)
+pub fn default_texture_files() -> Vec<(char, String)> {
+    TEXTURE_FILES.iter().map(|(ch, path)| (*ch, path.to_string())).collect()
+}
+
 impl TextureManager {
+    // Carga las imagenes y ademas las sube a la GPU (requiere ventana abierta)
     pub fn new(rl: &mut RaylibHandle, thread: &RaylibThread) -> Self {
+        TextureManager::new_from(rl, thread, &default_texture_files())
+    }
+
+    // Igual que `new` pero con un mapeo simbolo -> archivo propio
+    pub fn new_from(rl: &mut RaylibHandle, thread: &RaylibThread, files: &[(char, String)]) -> Self {
+        let mut manager = TextureManager::load_headless_from(files);
+
+        for (ch, path) in files {
+            if !manager.images.contains_key(ch) { continue; }
+            match rl.load_texture(thread, path) {
+                Ok(texture) => { manager.textures.insert(*ch, texture); }
+                Err(e) => eprintln!("No se pudo crear la textura {}: {}", path, e),
+            }
+        }
+
+        manager
+    }
+
+    // Solo decodifica las imagenes en memoria; no necesita ventana ni contexto de GPU.
+    // Si falta un archivo se avisa y ese simbolo usa su color plano del mapa.
+    pub fn load_headless() -> Self {
+        TextureManager::load_headless_from(&default_texture_files())
+    }
+
+    pub fn load_headless_from(files: &[(char, String)]) -> Self {
         let mut images = HashMap::new();
-        let mut textures = HashMap::new();
-
-        // Mapeo de caracteres en assets
-        let texture_files = vec![
-            ('+', "assets/wall1.png"),
-            ('-', "assets/wall2.png"),
-            ('|', "assets/wall3.png"),
-            ('g', "assets/wall4.png"),
-            (' ', "assets/floor.png"),
-            ('c', "assets/hungrycorgi.png"),
-            ('a', "assets/angricorgi.png"),
-            ('b', "assets/hungrybullterrier.png"),
-            ('m', "assets/hungrymastif.png"),
-        ];
-
-        for (ch, path) in texture_files {
-            let image = Image::load_image(path).expect(&format!("Failed to load image {}", path));
-            let texture = rl.load_texture(thread, path).expect(&format!("Failed to load texture {}", path));
-            images.insert(ch, image);
-            textures.insert(ch, texture);
+
+        for (ch, path) in files {
+            match Image::load_image(path) {
+                Ok(image) => { images.insert(*ch, TexImage::from_image(&image)); }
+                Err(e) => eprintln!("No se pudo cargar la imagen {}: {}", path, e),
+            }
         }
 
-        TextureManager { images, textures }
+        TextureManager { images, textures: HashMap::new() }
     }
 
     pub fn get_pixel_color(&self, ch: char, tx: u32, ty: u32) -> Color {
         if let Some(image) = self.images.get(&ch) {
-            let x = tx.min(image.width as u32 - 1) as i32;
-            let y = ty.min(image.height as u32 - 1) as i32;
-            get_pixel_color(image, x, y)
+            let x = tx.min(image.width - 1);
+            let y = ty.min(image.height - 1);
+            image.pixels[(y * image.width + x) as usize]
         } else {
-            Color::WHITE
+            symbol_to_color(ch)
         }
     }
 
@@ -52,34 +120,9 @@ impl TextureManager {
 
     pub fn get_image_size(&self, ch: char) -> (u32, u32) {
         if let Some(img) = self.images.get(&ch) {
-            (img.width as u32, img.height as u32)
+            (img.width, img.height)
         } else {
             (1, 1)
         }
     }
 }
-
-fn get_pixel_color(image: &Image, x: i32, y: i32) -> Color {
-    let width = image.width as usize;
-    let height = image.height as usize;
-
-    if x < 0 || y < 0 || x as usize >= width || y as usize >= height {
-        return Color::WHITE;
-    }
-
-    let x = x as usize;
-    let y = y as usize;
-
-    let data_len = width * height * 4;
-
-    unsafe {
-        let data = slice::from_raw_parts(image.data as *const u8, data_len);
-        let idx = (y * width + x) * 4;
-
-        if idx + 3 >= data_len {
-            return Color::WHITE;
-        }
-
-        Color::new(data[idx], data[idx + 1], data[idx + 2], data[idx + 3])
-    }
-}
@@ -0,0 +1,154 @@
+// src/validate.rs
+// Revisa que un laberinto sea jugable: cerrado por paredes, con spawn y con la
+// salida 'g' alcanzable desde el spawn sin subir escalones mas altos que los que
+// sube el jugador ni pasar por cerraduras cuya llave no se consigue antes.
+
+use std::fmt;
+
+use crate::doors::{Doors, KeyColor};
+use crate::level::{Level, key_cells};
+use crate::maze::{Maze, find_first_free_cell, is_wall};
+use crate::pathfinding::{Cell, cell_of, distance_field, goal_cells, reach_field, unlock_reachable};
+use crate::terrain::{Terrain, WALL_HEIGHT};
+use crate::walls::{is_see_through, is_thin};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationIssue {
+    // No hay ninguna celda libre donde aparecer
+    NoSpawn,
+    // No hay ninguna celda de salida 'g'
+    NoGoal,
+    // Ninguna salida tiene una celda vecina alcanzable desde el spawn
+    GoalUnreachable { goal: (usize, usize) },
+    // Celda del borde que no es una pared opaca entera: un rayo puede salir del mapa
+    // por aqui
+    OpenBorder { cell: (usize, usize) },
+    // Grupo de celdas libres a las que no se llega desde el spawn
+    UnreachablePocket { cell: (usize, usize), size: usize },
+}
+
+impl ValidationIssue {
+    // Los bolsillos inalcanzables son solo un aviso; el resto impide jugar bien el nivel
+    pub fn is_error(&self) -> bool {
+        !matches!(self, ValidationIssue::UnreachablePocket { .. })
+    }
+}
+
+pub struct ValidationReport {
+    pub spawn: Option<(usize, usize)>,
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        !self.issues.iter().any(|i| i.is_error())
+    }
+}
+
+// Las posiciones se muestran como linea/columna del archivo (desde 1)
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ValidationIssue::NoSpawn => write!(f, "no hay celdas libres para el spawn"),
+            ValidationIssue::NoGoal => write!(f, "no hay salida 'g'"),
+            ValidationIssue::GoalUnreachable { goal: (x, y) } => write!(
+                f, "linea {}, columna {}: la salida no se alcanza desde el spawn", y + 1, x + 1
+            ),
+            ValidationIssue::OpenBorder { cell: (x, y) } => write!(
+                f, "linea {}, columna {}: celda abierta en el borde, los rayos escapan del mapa",
+                y + 1, x + 1
+            ),
+            ValidationIssue::UnreachablePocket { cell: (x, y), size } => write!(
+                f, "linea {}, columna {}: {} celda(s) libres inalcanzables desde el spawn",
+                y + 1, x + 1, size
+            ),
+        }
+    }
+}
+
+fn cell_at(maze: &Maze, x: isize, y: isize) -> Option<char> {
+    if x < 0 || y < 0 { return None; }
+    maze.get(y as usize)?.get(x as usize).copied()
+}
+
+const NEIGHBORS: [(isize, isize); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+// Laberinto suelto: el spawn es la primera celda libre, como en level_from_maze, y
+// no hay llaves (las cerraduras quedan cerradas)
+pub fn validate_maze(maze: &Maze, terrain: &Terrain) -> ValidationReport {
+    validate(maze, terrain, find_first_free_cell(maze), &[])
+}
+
+// Nivel cargado: se valida desde el spawn que declara el nivel, con sus llaves
+pub fn validate_level(level: &Level) -> ValidationReport {
+    let spawn = cell_of(level.spawn.x, level.spawn.y);
+    validate(&level.maze, &level.terrain, spawn, &key_cells(&level.items))
+}
+
+fn validate(maze: &Maze, terrain: &Terrain, spawn: Option<Cell>, keys: &[(KeyColor, Cell)]) -> ValidationReport {
+    let mut issues = Vec::new();
+
+    // Cerramiento: solo una pared opaca de bloque entero y al menos de la altura comun
+    // frena los rayos; cualquier otra celda (libre, reja, puerta, pilar, media pared)
+    // no puede tocar el exterior de la grilla
+    for (y, row) in maze.iter().enumerate() {
+        for (x, &c) in row.iter().enumerate() {
+            let encloses = is_wall(c)
+                && !is_thin(c)
+                && !is_see_through(c)
+                && terrain.wall_height((x, y)) >= WALL_HEIGHT;
+            if encloses { continue; }
+            let open = NEIGHBORS
+                .iter()
+                .any(|(dx, dy)| cell_at(maze, x as isize + dx, y as isize + dy).is_none());
+            if open {
+                issues.push(ValidationIssue::OpenBorder { cell: (x, y) });
+            }
+        }
+    }
+
+    if spawn.is_none() {
+        issues.push(ValidationIssue::NoSpawn);
+    }
+
+    // Puertas: las cerraduras que se abren con las llaves alcanzables desde el spawn;
+    // las demas cortan el paso
+    let doors = match spawn {
+        Some(s) => unlock_reachable(maze, &Doors::from_maze(maze), terrain, s, keys),
+        None => Doors::from_maze(maze),
+    };
+
+    // Salida: alcanzable si el campo de distancia hacia las 'g' llega al spawn
+    let goals = goal_cells(maze);
+    if goals.is_empty() {
+        issues.push(ValidationIssue::NoGoal);
+    } else if let Some(s) = spawn
+        && !distance_field(maze, &doors, terrain, &goals).reaches(s)
+    {
+        issues.push(ValidationIssue::GoalUnreachable { goal: goals[0] });
+    }
+
+    // Bolsillos: cada grupo de celdas libres al que no llega el campo desde el spawn
+    if let Some(s) = spawn {
+        let mut seen: Vec<Vec<bool>> = maze.iter().map(|row| vec![false; row.len()]).collect();
+        for (x, y) in reach_field(maze, &doors, terrain, &[s]).reached() {
+            seen[y][x] = true;
+        }
+        for y in 0..maze.len() {
+            for x in 0..maze[y].len() {
+                if is_wall(maze[y][x]) || seen[y][x] { continue; }
+                let mut size = 0;
+                // bajando de un escalon alto se puede volver a celdas ya vistas
+                for (px, py) in reach_field(maze, &doors, terrain, &[(x, y)]).reached() {
+                    if !seen[py][px] {
+                        seen[py][px] = true;
+                        size += 1;
+                    }
+                }
+                issues.push(ValidationIssue::UnreachablePocket { cell: (x, y), size });
+            }
+        }
+    }
+
+    ValidationReport { spawn, issues }
+}
@@ -0,0 +1,85 @@
+// src/walls.rs
+// Paredes finas: ocupan solo parte de la celda en vez del bloque entero.
+//  - '_', '~', '[' y ']' son paredes sobre un borde de la celda (sur, norte, oeste y
+//    este). La celda se puede pisar; lo que no se puede es cruzar ese borde.
+//  - 'F' (reja) y 'W' (ventana) son paneles a mitad de la celda, orientados como las
+//    puertas segun las paredes vecinas, y 'I' es un pilar en el centro. Estas celdas
+//    no se pueden pisar.
+//  - '#' (rejas) y 'H' (seto) son bloques enteros. Junto con la reja y la ventana
+//    tienen texturas con transparencia: el rayo sigue de largo y se ve lo de atras.
+
+use crate::caster::Face;
+use crate::doors::{door_along_x, is_door};
+use crate::maze::Maze;
+use crate::pathfinding::Cell;
+
+// Medio lado del pilar (en celdas)
+pub const PILLAR_HALF: f32 = 0.2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WallShape {
+    // Bloque que llena la celda
+    Block,
+    // Plano sobre el borde `Face` de la celda
+    Edge(Face),
+    // Plano a mitad de la celda; `along_x` si corre a lo largo del eje x
+    Panel { along_x: bool },
+    // Caja centrada de medio lado PILLAR_HALF
+    Pillar,
+}
+
+// Borde que ocupa una pared de borde
+pub fn edge_face(c: char) -> Option<Face> {
+    match c {
+        '_' => Some(Face::South),
+        '~' => Some(Face::North),
+        '[' => Some(Face::West),
+        ']' => Some(Face::East),
+        _ => None,
+    }
+}
+
+// Paredes cuya textura tiene huecos o es translucida
+pub fn is_see_through(c: char) -> bool {
+    matches!(c, 'F' | 'W' | '#' | 'H')
+}
+
+// true para los simbolos que no llenan la celda (las puertas tambien son paneles)
+pub fn is_thin(c: char) -> bool {
+    edge_face(c).is_some() || matches!(c, 'F' | 'W' | 'I') || is_door(c)
+}
+
+// Forma de la celda `cell` con simbolo `c`
+pub fn wall_shape(maze: &Maze, cell: Cell, c: char) -> WallShape {
+    if let Some(face) = edge_face(c) {
+        return WallShape::Edge(face);
+    }
+    match c {
+        'I' => WallShape::Pillar,
+        'F' | 'W' => WallShape::Panel { along_x: door_along_x(maze, cell) },
+        _ if is_door(c) => WallShape::Panel { along_x: door_along_x(maze, cell) },
+        _ => WallShape::Block,
+    }
+}
+
+// Paso a la cara de la celda que mira hacia (dx, dy)
+fn face_towards(dx: isize, dy: isize) -> Option<Face> {
+    match (dx, dy) {
+        (1, 0) => Some(Face::East),
+        (-1, 0) => Some(Face::West),
+        (0, 1) => Some(Face::South),
+        (0, -1) => Some(Face::North),
+        _ => None,
+    }
+}
+
+fn has_edge(maze: &Maze, (x, y): Cell, face: Face) -> bool {
+    maze.get(y).and_then(|row| row.get(x)).and_then(|&c| edge_face(c)) == Some(face)
+}
+
+// true si una pared de borde separa dos celdas vecinas (en cualquiera de las dos)
+pub fn edge_between(maze: &Maze, a: Cell, b: Cell) -> bool {
+    let (dx, dy) = (b.0 as isize - a.0 as isize, b.1 as isize - a.1 as isize);
+    let (Some(out), Some(back)) = (face_towards(dx, dy), face_towards(-dx, -dy)) else { return false };
+    has_edge(maze, a, out) || has_edge(maze, b, back)
+}
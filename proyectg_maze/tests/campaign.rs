@@ -0,0 +1,115 @@
+// tests/campaign.rs
+// Manifiesto de la campaña, niveles generados con semilla y progreso guardado.
+
+use proyectg_maze::campaign::{LevelSource, Progress, load_campaign, parse_campaign};
+use proyectg_maze::level::LevelError;
+use proyectg_maze::maze::generate::Algorithm;
+use proyectg_maze::stats::LevelStats;
+use proyectg_maze::validate::validate_level;
+
+const MANIFEST: &str = r#"
+version = 1
+name = "Prueba"
+
+[[levels]]
+file = "uno.toml"
+
+[[levels]]
+name = "Dos"
+generate = { algorithm = "prim", width = 6, height = 5, seed = 3, braid = 0.5 }
+"#;
+
+fn stats(elapsed: f32, steps: u32) -> LevelStats {
+    LevelStats { elapsed, steps, distance: 0.0 }
+}
+
+#[test]
+fn parses_file_and_generated_entries() {
+    let campaign = parse_campaign(MANIFEST, "niveles/campaign.toml").unwrap();
+    assert_eq!(campaign.name, "Prueba");
+    assert_eq!(campaign.len(), 2);
+    // el nombre por defecto de un archivo es su nombre sin extension
+    assert_eq!(campaign.entries[0].name, "uno");
+    assert_eq!(campaign.entries[0].source, LevelSource::File("niveles/uno.toml".to_string()));
+    match &campaign.entries[1].source {
+        LevelSource::Generated { algorithm, width, height, seed, options } => {
+            assert_eq!((*algorithm, *width, *height, *seed), (Algorithm::Prim, 6, 5, 3));
+            assert_eq!(options.braid, 0.5);
+        }
+        other => panic!("se esperaba un nivel generado: {:?}", other),
+    }
+}
+
+#[test]
+fn generated_levels_are_reproducible() {
+    let campaign = parse_campaign(MANIFEST, "campaign.toml").unwrap();
+    let a = campaign.load(1).unwrap();
+    let b = campaign.load(1).unwrap();
+    assert_eq!(a.name, "Dos");
+    assert_eq!(a.maze, b.maze);
+    assert_eq!((a.maze[0].len(), a.maze.len()), (13, 11));
+    assert!(a.sprites.is_empty());
+}
+
+#[test]
+fn rejects_bad_entries() {
+    let both = "version = 1\n[[levels]]\nfile = \"a.txt\"\ngenerate = { algorithm = \"prim\", width = 4, height = 4, seed = 1 }\n";
+    let duplicated = "version = 1\n[[levels]]\nfile = \"a.txt\"\n[[levels]]\nfile = \"otra/a.toml\"\n";
+    let algorithm = "version = 1\n[[levels]]\ngenerate = { algorithm = \"dfs\", width = 4, height = 4, seed = 1 }\n";
+    let empty = "version = 1\nlevels = []\n";
+    for text in [both, duplicated, algorithm, empty] {
+        assert!(matches!(parse_campaign(text, "c.toml"), Err(LevelError::Invalid { .. })), "{}", text);
+    }
+    assert!(matches!(
+        parse_campaign("version = 2\nlevels = []\n", "c.toml"),
+        Err(LevelError::UnsupportedVersion { version: 2, .. })
+    ));
+}
+
+#[test]
+fn bundled_campaign_levels_are_valid() {
+    let campaign = load_campaign("levels/campaign.toml").unwrap();
+    assert!(campaign.len() >= 3);
+    for i in 0..campaign.len() {
+        let level = campaign.load(i).unwrap();
+        assert_eq!(level.name, campaign.entries[i].name);
+        assert!(validate_level(&level).is_valid(), "{}", level.name);
+    }
+}
+
+#[test]
+fn completing_levels_unlocks_the_next_one() {
+    let campaign = parse_campaign(MANIFEST, "campaign.toml").unwrap();
+    let mut progress = Progress::default();
+    assert_eq!(progress.unlocked(&campaign), 1);
+
+    progress.record("uno", &stats(30.0, 40));
+    assert_eq!(progress.unlocked(&campaign), 2);
+    // completar todo no desbloquea mas niveles de los que hay
+    progress.record("Dos", &stats(10.0, 12));
+    assert_eq!(progress.unlocked(&campaign), 2);
+
+    // se guarda el mejor tiempo y la menor cantidad de pasos por separado
+    progress.record("uno", &stats(25.0, 50));
+    let record = &progress.completed["uno"];
+    assert_eq!((record.best_time, record.best_steps), (25.0, 40));
+}
+
+#[test]
+fn progress_round_trips_through_file() {
+    let path = std::env::temp_dir().join(format!("proyectg_progress_{}.toml", std::process::id()));
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+
+    // sin archivo se empieza de cero
+    assert_eq!(Progress::load(path).unwrap(), Progress::default());
+
+    let mut progress = Progress::default();
+    progress.record("Laberinto Verde", &stats(41.5, 63));
+    progress.save(path).unwrap();
+    assert_eq!(Progress::load(path).unwrap(), progress);
+
+    std::fs::write(path, "version = 1\ncompleted = 3\n").unwrap();
+    assert!(matches!(Progress::load(path), Err(LevelError::Syntax { .. })));
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,162 @@
+// tests/doors.rs
+// Puertas corredizas y con cerradura: apertura con el tiempo, llaves, colision y rayos.
+
+use raylib::prelude::Vector2;
+
+use proyectg_maze::caster::cast_ray_dir;
+use proyectg_maze::controller::cell_is_free;
+use proyectg_maze::doors::{DoorKind, Doors, KeyColor};
+use proyectg_maze::items::{Inventory, ItemKind};
+use proyectg_maze::level::{LevelError, load_level, parse_level};
+use proyectg_maze::maze::{Maze, parse_maze};
+use proyectg_maze::pathfinding::{distance_field, find_path};
+use proyectg_maze::terrain::Terrain;
+
+const DT: f32 = 1.0 / 30.0;
+
+// Pasillo con una puerta en la columna 4
+fn corridor(door: char) -> Maze {
+    let text = format!("+---+---+\n|   {}   |\n+---+---+\n", door);
+    parse_maze(&text, "test").unwrap()
+}
+
+#[test]
+fn sliding_door_opens_near_the_player_and_closes_later() {
+    let m = corridor('D');
+    let mut doors = Doors::from_maze(&m);
+    assert!(!cell_is_free(&m, &doors, 4, 1));
+    // sin estado las puertas cuentan como abiertas
+    assert!(cell_is_free(&m, &Doors::NONE, 4, 1));
+
+    // lejos no pasa nada
+    doors.update(Vector2::new(1.5, 1.5), &[], &[], DT);
+    assert_eq!(doors.open_fraction((4, 1)), 0.0);
+
+    for _ in 0..30 {
+        doors.update(Vector2::new(3.5, 1.5), &[], &[], DT);
+    }
+    assert_eq!(doors.open_fraction((4, 1)), 1.0);
+    assert!(cell_is_free(&m, &doors, 4, 1));
+
+    // el jugador se aleja: espera un poco y se cierra
+    for _ in 0..30 {
+        doors.update(Vector2::new(1.5, 1.5), &[], &[], DT);
+    }
+    assert_eq!(doors.open_fraction((4, 1)), 1.0);
+    for _ in 0..90 {
+        doors.update(Vector2::new(1.5, 1.5), &[], &[], DT);
+    }
+    assert_eq!(doors.open_fraction((4, 1)), 0.0);
+}
+
+#[test]
+fn locked_door_needs_its_key() {
+    let m = corridor('R');
+    let mut doors = Doors::from_maze(&m);
+    assert_eq!(doors.get((4, 1)).unwrap().kind, DoorKind::Locked(KeyColor::Red));
+
+    // ni el jugador con otra llave ni un perro la abren
+    let near = Vector2::new(3.5, 1.5);
+    for _ in 0..30 {
+        doors.update(near, &[KeyColor::Blue], &[near], DT);
+    }
+    assert_eq!(doors.open_fraction((4, 1)), 0.0);
+
+    doors.update(near, &[KeyColor::Red], &[], DT);
+    assert!(doors.open_fraction((4, 1)) > 0.0);
+    assert!(!doors.get((4, 1)).unwrap().is_locked());
+
+    // una vez abierta queda como corrediza: el perro tambien la abre
+    let mut doors_dog = doors.clone();
+    for _ in 0..30 {
+        doors_dog.update(Vector2::new(1.5, 1.5), &[], &[near], DT);
+    }
+    assert!(cell_is_free(&m, &doors_dog, 4, 1));
+
+    let mut inv = Inventory::new();
+    assert!(inv.add(ItemKind::Key(KeyColor::Red)));
+    assert!(inv.add(ItemKind::Key(KeyColor::Red)));
+    assert_eq!(inv.keys, vec![KeyColor::Red]);
+    assert!(inv.items.is_empty());
+}
+
+#[test]
+fn rays_hit_the_closed_part_of_the_recessed_panel() {
+    let m = corridor('D');
+    let mut doors = Doors::from_maze(&m);
+    let right = Vector2::new(1.0, 0.0);
+
+    // cerrada: la hoja esta a mitad de la celda
+    let hit = cast_ray_dir(&m, &doors, Vector2::new(1.5, 1.5), right).unwrap();
+    assert_eq!(hit.impact, 'D');
+    assert!((hit.distance - 3.0).abs() < 1e-4, "{}", hit.distance);
+
+    // abierta a la mitad: el rayo pasa por la parte baja y choca con la parte alta
+    doors.update(Vector2::new(3.5, 1.5), &[], &[], 1.0 / 3.0);
+    assert!((doors.open_fraction((4, 1)) - 0.5).abs() < 1e-4);
+    let low = cast_ray_dir(&m, &doors, Vector2::new(1.5, 1.2), right).unwrap();
+    assert_eq!((low.impact, low.cell_x), ('|', 8));
+    let high = cast_ray_dir(&m, &doors, Vector2::new(1.5, 1.8), right).unwrap();
+    assert_eq!((high.impact, high.cell_x), ('D', 4));
+
+    // mientras se vea un pedazo de hoja no se puede pasar
+    doors.update(Vector2::new(3.5, 1.5), &[], &[], 0.3);
+    assert!((doors.open_fraction((4, 1)) - 0.95).abs() < 1e-4);
+    assert!(!cell_is_free(&m, &doors, 4, 1));
+    let edge = cast_ray_dir(&m, &doors, Vector2::new(1.5, 1.98), right).unwrap();
+    assert_eq!((edge.impact, edge.cell_x), ('D', 4));
+
+    // sin estado no hay hoja
+    let open = cast_ray_dir(&m, &Doors::NONE, Vector2::new(1.5, 1.5), right).unwrap();
+    assert_eq!(open.impact, '|');
+}
+
+#[test]
+fn levels_need_a_key_for_every_lock() {
+    let grid = "grid = '''\n+---+---+\n|   R   g\n+---+---+\n'''\n";
+    let text = format!("version = 1\n{}", grid);
+    assert!(matches!(parse_level(&text, "t.toml"), Err(LevelError::Invalid { .. })));
+
+    let text = format!("version = 1\n{}[[items]]\nx = 1.5\ny = 1.5\nkind = \"red_key\"\n", grid);
+    let level = parse_level(&text, "t.toml").unwrap();
+    assert_eq!(level.items[0].kind, ItemKind::Key(KeyColor::Red));
+    assert_eq!(level.items[0].sprite.tex_keys, vec!['r']);
+    assert_eq!(level.doors.open_fraction((4, 1)), 0.0);
+
+    // la llave del otro lado de su propia cerradura no sirve
+    let text = format!("version = 1\n{}[[items]]\nx = 6.5\ny = 1.5\nkind = \"red_key\"\n", grid);
+    let err = parse_level(&text, "t.toml").err().unwrap();
+    assert!(err.to_string().contains("puerta red en (4, 1) no se puede abrir"), "{}", err);
+
+    // llaves cruzadas: la azul detras de la puerta roja y la roja detras de la azul
+    let grid = "grid = '''\n+---+---+\n|   R   |\n|   +---+\n|   B   |\n+---+---+\n'''\n";
+    let key = |x: f32, y: f32, kind: &str| format!("[[items]]\nx = {}\ny = {}\nkind = \"{}\"\n", x, y, kind);
+    let crossed = format!("version = 1\n{}{}{}", grid, key(6.5, 3.5, "red_key"), key(6.5, 1.5, "blue_key"));
+    let err = parse_level(&crossed, "t.toml").err().unwrap();
+    assert!(err.to_string().contains("no se puede abrir"), "{}", err);
+    // con la roja del lado del spawn se abre una puerta y despues la otra
+    let chained = format!("version = 1\n{}{}{}", grid, key(2.5, 2.5, "red_key"), key(6.5, 1.5, "blue_key"));
+    assert!(parse_level(&chained, "t.toml").is_ok());
+}
+
+#[test]
+fn text_levels_cannot_have_locks() {
+    let path = std::env::temp_dir().join(format!("proyectg_locked_{}.txt", std::process::id()));
+    std::fs::write(&path, "+---+---+\n|   B   g\n+---+---+\n").unwrap();
+    let err = load_level(path.to_str().unwrap()).err().unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert!(matches!(err, LevelError::Invalid { .. }));
+    assert!(err.to_string().contains("puerta blue en (4, 1)"), "{}", err);
+}
+
+#[test]
+fn paths_do_not_cross_locked_doors() {
+    let m = corridor('R');
+    let mut doors = Doors::from_maze(&m);
+    assert_eq!(find_path(&m, &doors, &Terrain::FLAT, (1, 1), (7, 1)), None);
+    assert!(!distance_field(&m, &doors, &Terrain::FLAT, &[(1, 1)]).reaches((7, 1)));
+    // las corredizas y las cerraduras ya abiertas se cruzan
+    assert!(find_path(&m, &Doors::NONE, &Terrain::FLAT, (1, 1), (7, 1)).is_some());
+    doors.unlock(KeyColor::Red);
+    assert_eq!(find_path(&m, &doors, &Terrain::FLAT, (1, 1), (7, 1)).unwrap().len(), 7);
+}
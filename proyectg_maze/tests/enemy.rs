@@ -0,0 +1,121 @@
+// tests/enemy.rs
+// Maquina de estados de los perros: vista, persecucion, regreso y paseo.
+
+use raylib::prelude::Vector2;
+
+use proyectg_maze::controller::cell_is_free;
+use proyectg_maze::doors::Doors;
+use proyectg_maze::enemy::{AiState, DogKind, can_see, default_dog_stats, spawn_enemies, update_enemies};
+use proyectg_maze::maze::{Maze, parse_maze};
+use proyectg_maze::sprites::Sprite;
+use proyectg_maze::terrain::Terrain;
+
+// Un pasillo largo arriba y una sala cerrada abajo a la derecha
+const MAP: &str = "\
++-----------+
+|           |
++-------+-+ |
+|       | | |
+|       +-+ |
+|           |
++-----------+
+";
+
+fn maze() -> Maze {
+    parse_maze(MAP, "test").unwrap()
+}
+
+fn sprite(x: f32, y: f32, key: char) -> Sprite {
+    Sprite {
+        pos: Vector2::new(x, y),
+        tex_keys: vec![key],
+        current_frame: 0,
+        frame_time: 0.1,
+        timer: 0.0,
+        size: 1.0,
+    }
+}
+
+const DT: f32 = 1.0 / 30.0;
+
+#[test]
+fn only_dog_sprites_get_an_ai() {
+    let mut sprites = vec![sprite(1.5, 1.5, 'c'), sprite(2.5, 1.5, 'g'), sprite(3.5, 1.5, 'm')];
+    let enemies = spawn_enemies(&mut sprites, &default_dog_stats(), 1);
+    assert_eq!(enemies.len(), 2);
+    assert_eq!((enemies[0].sprite, enemies[0].kind), (0, DogKind::Corgi));
+    assert_eq!((enemies[1].sprite, enemies[1].kind), (2, DogKind::Mastiff));
+    assert!(enemies.iter().all(|e| e.state == AiState::Idle));
+}
+
+#[test]
+fn walls_and_distance_block_sight() {
+    let m = maze();
+    assert!(can_see(&m, &Doors::NONE, Vector2::new(1.5, 1.5), Vector2::new(6.5, 1.5), 7.0));
+    assert!(!can_see(&m, &Doors::NONE, Vector2::new(1.5, 1.5), Vector2::new(10.5, 1.5), 7.0));
+    // la pared de la fila 2 separa el pasillo de la sala
+    assert!(!can_see(&m, &Doors::NONE, Vector2::new(2.5, 1.5), Vector2::new(2.5, 3.5), 7.0));
+}
+
+#[test]
+fn corgi_chases_with_angry_frame_and_stops_near_player() {
+    let m = maze();
+    let mut sprites = vec![sprite(1.5, 1.5, 'c')];
+    let mut enemies = spawn_enemies(&mut sprites, &default_dog_stats(), 1);
+    let player = Vector2::new(5.5, 1.5);
+
+    update_enemies(&mut enemies, &mut sprites, &m, &Doors::NONE, &Terrain::FLAT, player, &mut Vec::new(), DT);
+    assert_eq!(enemies[0].state, AiState::Chase);
+    assert_eq!(sprites[0].tex_keys, vec!['a']);
+
+    for _ in 0..120 {
+        update_enemies(&mut enemies, &mut sprites, &m, &Doors::NONE, &Terrain::FLAT, player, &mut Vec::new(), DT);
+    }
+    let gap = (player - sprites[0].pos).length();
+    assert!(gap > 0.5 && gap < 0.7, "distancia final {}", gap);
+}
+
+#[test]
+fn lost_player_makes_dog_return_home() {
+    let m = maze();
+    let mut sprites = vec![sprite(1.5, 5.5, 'b')];
+    let mut enemies = spawn_enemies(&mut sprites, &default_dog_stats(), 1);
+
+    // lo ve en la sala y lo sigue; despues el jugador queda detras de la pared
+    for _ in 0..30 {
+        update_enemies(&mut enemies, &mut sprites, &m, &Doors::NONE, &Terrain::FLAT, Vector2::new(6.5, 5.5), &mut Vec::new(), DT);
+    }
+    assert_eq!(enemies[0].state, AiState::Chase);
+    assert!(sprites[0].pos.x > 3.0);
+
+    let hidden = Vector2::new(1.5, 1.5);
+    let mut returned = false;
+    for _ in 0..600 {
+        update_enemies(&mut enemies, &mut sprites, &m, &Doors::NONE, &Terrain::FLAT, hidden, &mut Vec::new(), DT);
+        if enemies[0].state == AiState::Return { returned = true; }
+        if returned && enemies[0].state == AiState::Idle { break; }
+    }
+    assert!(returned);
+    assert_eq!(enemies[0].state, AiState::Idle);
+    assert_eq!(sprites[0].pos, Vector2::new(1.5, 5.5));
+    assert_eq!(sprites[0].tex_keys, vec!['b']);
+}
+
+#[test]
+fn wandering_stays_on_free_cells_near_home() {
+    let m = maze();
+    let mut sprites = vec![sprite(6.5, 1.5, 'm')];
+    let mut enemies = spawn_enemies(&mut sprites, &default_dog_stats(), 7);
+    // el jugador esta fuera de la vista del mastin
+    let player = Vector2::new(1.5, 5.5);
+    let mut wandered = false;
+    for _ in 0..1200 {
+        update_enemies(&mut enemies, &mut sprites, &m, &Doors::NONE, &Terrain::FLAT, player, &mut Vec::new(), DT);
+        let p = sprites[0].pos;
+        assert!(cell_is_free(&m, &Doors::NONE, p.x.floor() as isize, p.y.floor() as isize), "{:?}", p);
+        assert!((p.x - 6.5).abs() <= 4.5 && (p.y - 1.5).abs() <= 4.5);
+        wandered |= enemies[0].state == AiState::Wander;
+        assert_ne!(enemies[0].state, AiState::Chase);
+    }
+    assert!(wandered);
+}
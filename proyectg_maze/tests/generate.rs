@@ -0,0 +1,110 @@
+// tests/generate.rs
+// Generador de laberintos: determinismo, solubilidad y laberintos perfectos.
+
+use proyectg_maze::maze::generate::{generate, generate_with, Algorithm, GenOptions};
+use proyectg_maze::maze::metrics::difficulty;
+use proyectg_maze::maze::{is_wall, parse_maze};
+use proyectg_maze::validate::validate_maze;
+use proyectg_maze::terrain::Terrain;
+
+#[test]
+fn same_seed_same_maze() {
+    for algorithm in Algorithm::ALL {
+        assert_eq!(generate(9, 7, algorithm, 1234), generate(9, 7, algorithm, 1234), "{:?}", algorithm);
+        assert_ne!(generate(9, 7, algorithm, 1234), generate(9, 7, algorithm, 4321), "{:?}", algorithm);
+    }
+}
+
+#[test]
+fn every_algorithm_is_valid_and_fully_connected() {
+    for algorithm in Algorithm::ALL {
+        for seed in 0..20 {
+            let maze = generate(11, 6, algorithm, seed);
+            assert_eq!((maze[0].len(), maze.len()), (23, 13));
+            let report = validate_maze(&maze, &Terrain::FLAT);
+            // valido y sin bolsillos: todas las celdas se alcanzan desde el spawn
+            assert!(report.issues.is_empty(), "{:?} semilla {}: {:?}", algorithm, seed, report.issues);
+            assert_eq!(maze.iter().flatten().filter(|&&c| c == 'g').count(), 1);
+        }
+    }
+}
+
+#[test]
+fn mazes_are_perfect() {
+    // un arbol de w*h celdas tiene exactamente w*h - 1 pasos abiertos
+    let (w, h) = (8, 5);
+    for algorithm in Algorithm::ALL {
+        let maze = generate(w, h, algorithm, 99);
+        let mut passages = 0;
+        for y in 0..h {
+            for x in 0..w {
+                let (mx, my) = (2 * x + 1, 2 * y + 1);
+                if x + 1 < w && !is_wall(maze[my][mx + 1]) { passages += 1; }
+                if y + 1 < h && !is_wall(maze[my + 1][mx]) { passages += 1; }
+            }
+        }
+        assert_eq!(passages, w * h - 1, "{:?}", algorithm);
+    }
+}
+
+#[test]
+fn tiny_sizes_still_work() {
+    for algorithm in Algorithm::ALL {
+        for (w, h) in [(1, 1), (1, 4), (5, 1), (0, 0)] {
+            assert!(validate_maze(&generate(w, h, algorithm, 7), &Terrain::FLAT).is_valid(), "{:?} {}x{}", algorithm, w, h);
+        }
+    }
+}
+
+#[test]
+fn algorithm_names_round_trip() {
+    for algorithm in Algorithm::ALL {
+        assert_eq!(Algorithm::from_name(algorithm.name()), Some(algorithm));
+    }
+    assert_eq!(Algorithm::from_name("dfs"), None);
+}
+
+#[test]
+fn options_keep_mazes_valid() {
+    let options = GenOptions { braid: 0.5, rooms: 3, plazas: 1, corridors: 2 };
+    for algorithm in Algorithm::ALL {
+        for seed in 0..10 {
+            let maze = generate_with(14, 9, algorithm, seed, &options);
+            let report = validate_maze(&maze, &Terrain::FLAT);
+            assert!(report.issues.is_empty(), "{:?} semilla {}: {:?}", algorithm, seed, report.issues);
+            assert_eq!(maze, generate_with(14, 9, algorithm, seed, &options));
+        }
+    }
+}
+
+#[test]
+fn full_braid_removes_every_dead_end() {
+    for algorithm in Algorithm::ALL {
+        let perfect = difficulty(&generate(10, 8, algorithm, 3));
+        let braided = difficulty(&generate_with(10, 8, algorithm, 3, &GenOptions { braid: 1.0, ..Default::default() }));
+        assert!(perfect.dead_ends > 0);
+        assert_eq!(braided.dead_ends, 0, "{:?}", algorithm);
+        assert!(braided.junctions > perfect.junctions);
+    }
+}
+
+#[test]
+fn rooms_have_no_inner_columns() {
+    let options = GenOptions { rooms: 1, ..Default::default() };
+    let plain = generate(6, 6, Algorithm::Kruskal, 11);
+    let roomy = generate_with(6, 6, Algorithm::Kruskal, 11, &options);
+    let posts = |m: &Vec<Vec<char>>| m.iter().flatten().filter(|&&c| c == '+').count();
+    assert!(posts(&roomy) < posts(&plain));
+}
+
+#[test]
+fn difficulty_of_hand_made_maze() {
+    let maze = parse_maze("+-----+\n|     |\n| +-+ |\n| |   g\n+-+---+\n", "test").unwrap();
+    let d = difficulty(&maze);
+    // (1,1) -> (5,1) -> (5,3) y un paso mas hasta la salida
+    assert_eq!(d.solution_length, Some(7));
+    assert_eq!(d.dead_ends, 2);
+    assert_eq!(d.junctions, 0);
+    assert_eq!(d.decisions, 0);
+    assert_eq!(d.free_cells, 11);
+}
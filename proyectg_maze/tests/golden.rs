@@ -0,0 +1,228 @@
+// tests/golden.rs
+// Pruebas de regresion visual del renderizador: cada escena se dibuja sin ventana
+// y se compara contra su imagen de referencia en tests/golden/<nombre>.ppm.
+// Si una escena falla se escriben <nombre>.actual.ppm y <nombre>.diff.ppm en
+// target/golden-diff. Con UPDATE_GOLDEN=1 se regeneran las referencias.
+
+use raylib::prelude::*;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use proyectg_maze::camera::{Camera, DEFAULT_FOV};
+use proyectg_maze::doors::Doors;
+use proyectg_maze::framebuffer::Framebuffer;
+use proyectg_maze::headless::render_frame;
+use proyectg_maze::maze::{load_maze, Maze};
+use proyectg_maze::player::Player;
+use proyectg_maze::sprites::Sprite;
+use proyectg_maze::terrain::Terrain;
+use proyectg_maze::textures::TextureManager;
+
+const WIDTH: u32 = 160;
+const HEIGHT: u32 = 120;
+
+// Diferencia maxima permitida por canal y fraccion de pixeles que pueden excederla
+const CHANNEL_TOLERANCE: u8 = 2;
+const MAX_BAD_FRACTION: f32 = 0.002;
+
+struct Scene {
+    name: &'static str,
+    maze: Maze,
+    pos: Vector2,
+    angle: f32,
+    fov: f32,
+    pitch: f32,
+    sprites: Vec<Sprite>,
+}
+
+impl Scene {
+    fn new(name: &'static str, maze: Maze, pos: (f32, f32), angle: f32) -> Self {
+        Scene {
+            name,
+            maze,
+            pos: Vector2::new(pos.0, pos.1),
+            angle,
+            fov: DEFAULT_FOV,
+            pitch: 0.0,
+            sprites: Vec::new(),
+        }
+    }
+
+    fn render(&self, tex: &TextureManager, threads: usize) -> Framebuffer {
+        let player = Player::new(self.pos, self.angle);
+        let mut camera = Camera::new(self.fov, WIDTH, HEIGHT);
+        camera.set_pitch(self.pitch);
+        camera.follow(&player);
+        render_frame(&self.maze, &Doors::from_maze(&self.maze), &Terrain::FLAT, &camera, &self.sprites, tex, threads)
+    }
+}
+
+fn maze_from(rows: &[&str]) -> Maze {
+    rows.iter().map(|r| r.chars().collect()).collect()
+}
+
+fn sprite(x: f32, y: f32, key: char) -> Sprite {
+    Sprite {
+        pos: Vector2::new(x, y),
+        tex_keys: vec![key],
+        current_frame: 0,
+        frame_time: 0.1,
+        timer: 0.0,
+        size: 1.0,
+    }
+}
+
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden")
+}
+
+fn diff_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target").join("golden-diff")
+}
+
+// Lee un PPM binario (P6) como (ancho, alto, rgb)
+fn read_ppm(path: &PathBuf) -> Option<(u32, u32, Vec<u8>)> {
+    let data = fs::read(path).ok()?;
+    let mut fields = Vec::new();
+    let mut pos = 0;
+    while fields.len() < 4 {
+        while data.get(pos)?.is_ascii_whitespace() { pos += 1; }
+        let start = pos;
+        while !data.get(pos)?.is_ascii_whitespace() { pos += 1; }
+        fields.push(String::from_utf8_lossy(&data[start..pos]).to_string());
+    }
+    if fields[0] != "P6" || fields[3] != "255" { return None; }
+    let w: u32 = fields[1].parse().ok()?;
+    let h: u32 = fields[2].parse().ok()?;
+    let rgb = data.get(pos + 1..)?.to_vec();
+    if rgb.len() != (w * h * 3) as usize { return None; }
+    Some((w, h, rgb))
+}
+
+fn check_golden(scene: &Scene, tex: &TextureManager) {
+    let frame = scene.render(tex, 1);
+    let golden_path = golden_dir().join(format!("{}.ppm", scene.name));
+
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(golden_dir()).unwrap();
+        frame.save_ppm(golden_path.to_str().unwrap()).unwrap();
+        return;
+    }
+
+    let (w, h, expected) = read_ppm(&golden_path).unwrap_or_else(|| {
+        panic!("Falta la referencia {:?}; ejecutar con UPDATE_GOLDEN=1", golden_path)
+    });
+    assert_eq!((w, h), (WIDTH, HEIGHT), "Tamaño distinto en {}", scene.name);
+
+    // Compara pixel a pixel y arma una imagen con las diferencias en rojo
+    let mut diff = Framebuffer::new(w, h);
+    let mut bad = 0;
+    for (i, c) in frame.pixels().iter().enumerate() {
+        let e = &expected[i * 3..i * 3 + 3];
+        let delta = c.r.abs_diff(e[0]).max(c.g.abs_diff(e[1])).max(c.b.abs_diff(e[2]));
+        let (x, y) = (i as i32 % w as i32, i as i32 / w as i32);
+        if delta > CHANNEL_TOLERANCE {
+            bad += 1;
+            diff.set_pixel_i32(x, y, Color::RED);
+        } else {
+            let g = (c.r as u16 + c.g as u16 + c.b as u16) as f32 / 3.0 * 0.3;
+            diff.set_pixel_i32(x, y, Color::new(g as u8, g as u8, g as u8, 255));
+        }
+    }
+
+    let bad_fraction = bad as f32 / (w * h) as f32;
+    if bad_fraction > MAX_BAD_FRACTION {
+        fs::create_dir_all(diff_dir()).unwrap();
+        let actual_path = diff_dir().join(format!("{}.actual.ppm", scene.name));
+        let diff_path = diff_dir().join(format!("{}.diff.ppm", scene.name));
+        frame.save_ppm(actual_path.to_str().unwrap()).unwrap();
+        diff.save_ppm(diff_path.to_str().unwrap()).unwrap();
+        panic!(
+            "{}: {} pixeles distintos ({:.2}%), ver {:?}",
+            scene.name, bad, bad_fraction * 100.0, diff_path
+        );
+    }
+}
+
+// Sala cerrada pequeña con la textura de '-' en todas las paredes
+fn room() -> Maze {
+    maze_from(&[
+        "-------",
+        "-     -",
+        "-     -",
+        "-     -",
+        "-     -",
+        "-------",
+    ])
+}
+
+#[test]
+fn golden_maze_corridor() {
+    let tex = TextureManager::load_headless();
+    let maze = load_maze("maze.txt").unwrap();
+    check_golden(&Scene::new("maze_corridor", maze, (1.5, 3.5), 0.2), &tex);
+}
+
+#[test]
+fn golden_maze_exit() {
+    let tex = TextureManager::load_headless();
+    let maze = load_maze("maze.txt").unwrap();
+    check_golden(&Scene::new("maze_exit", maze, (13.5, 5.5), 0.0), &tex);
+}
+
+#[test]
+fn golden_room_corner() {
+    // mirando justo a la esquina: prueba las caras y la textura en los bordes de celda
+    let tex = TextureManager::load_headless();
+    let scene = Scene::new("room_corner", room(), (2.5, 2.5), -std::f32::consts::FRAC_PI_4);
+    check_golden(&scene, &tex);
+}
+
+#[test]
+fn golden_room_wide_fov_pitch() {
+    let tex = TextureManager::load_headless();
+    let mut scene = Scene::new("room_wide_fov_pitch", room(), (1.5, 3.5), 0.3);
+    scene.fov = 1.6;
+    scene.pitch = 0.2;
+    check_golden(&scene, &tex);
+}
+
+#[test]
+fn golden_room_sprites() {
+    // un sprite a la vista y otro oculto detras de la pared del fondo
+    let tex = TextureManager::load_headless();
+    let mut scene = Scene::new("room_sprites", room(), (1.5, 2.5), 0.1);
+    scene.sprites = vec![sprite(4.0, 2.8, 'c'), sprite(3.2, 1.9, 'm'), sprite(8.0, 2.5, 'b')];
+    check_golden(&scene, &tex);
+}
+
+#[test]
+fn golden_floor_through_bars() {
+    // rejas en el medio y en el borde: el rayo no golpea nada opaco y el piso sigue
+    // texturado hasta el borde del mapa
+    let tex = TextureManager::load_headless();
+    let maze = maze_from(&[
+        "--------",
+        "-      #",
+        "-  #   #",
+        "-      #",
+        "--------",
+    ]);
+    check_golden(&Scene::new("floor_through_bars", maze, (1.5, 2.5), 0.0), &tex);
+}
+
+#[test]
+fn multithreaded_render_matches_single_thread() {
+    let tex = TextureManager::load_headless();
+    let mut scene = Scene::new("threads", load_maze("maze.txt").unwrap(), (1.5, 3.5), 0.2);
+    scene.sprites = vec![sprite(4.5, 3.5, 'c')];
+    let single = scene.render(&tex, 1);
+    let multi = scene.render(&tex, 4);
+    let same = single
+        .pixels()
+        .iter()
+        .zip(multi.pixels())
+        .all(|(a, b)| (a.r, a.g, a.b, a.a) == (b.r, b.g, b.b, b.a));
+    assert!(same, "el render multihilo difiere del de un solo hilo");
+}
@@ -0,0 +1,100 @@
+// tests/health.rs
+// Salud del jugador, daño por contacto con los perros y su configuracion por nivel.
+
+use raylib::prelude::{Color, Vector2};
+
+use proyectg_maze::enemy::{DogKind, DogStats, contact_damage, default_dog_stats, spawn_enemies};
+use proyectg_maze::framebuffer::Framebuffer;
+use proyectg_maze::level::{DEFAULT_HEALTH, LevelError, load_level, parse_level};
+use proyectg_maze::player::{Health, INVULNERABLE_TIME};
+use proyectg_maze::sprites::Sprite;
+
+fn sprite(x: f32, y: f32, key: char) -> Sprite {
+    Sprite {
+        pos: Vector2::new(x, y),
+        tex_keys: vec![key],
+        current_frame: 0,
+        frame_time: 0.1,
+        timer: 0.0,
+        size: 1.0,
+    }
+}
+
+#[test]
+fn hits_respect_invulnerability() {
+    let mut health = Health::new(100.0, None);
+    assert!(health.hit(30.0));
+    assert_eq!(health.hp, 70.0);
+    // recien golpeado no recibe mas daño
+    assert!(!health.hit(30.0));
+    health.update(INVULNERABLE_TIME * 0.5);
+    assert!(!health.hit(30.0));
+    health.update(INVULNERABLE_TIME * 0.5);
+    assert!(health.hit(80.0));
+    assert_eq!(health.hp, 0.0);
+    assert!(health.is_dead());
+    // sin vidas no hay segunda oportunidad
+    assert!(!health.lose_life());
+}
+
+#[test]
+fn lives_restore_health_until_the_last_one() {
+    let mut health = Health::new(50.0, Some(2));
+    health.hit(50.0);
+    assert!(health.lose_life());
+    assert_eq!((health.hp, health.lives), (50.0, Some(1)));
+    assert!(health.invulnerable > 0.0);
+    health.update(INVULNERABLE_TIME);
+    health.hit(50.0);
+    assert!(!health.lose_life());
+}
+
+#[test]
+fn contact_uses_radius_and_damage_of_each_dog() {
+    let mut sprites = vec![sprite(2.0, 1.0, 'c'), sprite(1.0, 2.0, 'm')];
+    let stats = vec![(DogKind::Corgi, DogStats { damage: 5.0, radius: 0.5 })];
+    let enemies = spawn_enemies(&mut sprites, &stats, 0);
+    assert_eq!(enemies[0].stats, DogStats { damage: 5.0, radius: 0.5 });
+    // el mastin no se configuro: valores por defecto
+    assert_eq!(enemies[1].stats, default_dog_stats()[2].1);
+
+    assert_eq!(contact_damage(&enemies, &sprites, Vector2::new(5.0, 5.0)), None);
+    assert_eq!(contact_damage(&enemies, &sprites, Vector2::new(1.4, 1.0)), Some(5.0));
+    // si lo tocan dos perros cuenta el golpe mas fuerte
+    let mastiff = default_dog_stats()[2].1.damage;
+    assert_eq!(contact_damage(&enemies, &sprites, Vector2::new(1.5, 1.5)), Some(mastiff));
+}
+
+#[test]
+fn level_configures_player_and_dogs() {
+    let level = load_level("levels/laberinto_verde.toml").unwrap();
+    assert_eq!((level.max_health, level.lives), (100.0, Some(3)));
+    let corgi = level.dog_stats.iter().find(|(k, _)| *k == DogKind::Corgi).unwrap().1;
+    assert_eq!(corgi.damage, 15.0);
+    assert_eq!(corgi.radius, default_dog_stats()[0].1.radius);
+
+    let txt = load_level("maze.txt").unwrap();
+    assert_eq!((txt.max_health, txt.lives), (DEFAULT_HEALTH, None));
+}
+
+#[test]
+fn rejects_bad_player_and_dog_settings() {
+    let grid = "grid = '''\n+---+\n|   g\n+---+\n'''\n";
+    for extra in ["[player]\nlives = 0\n", "[player]\nhealth = -5.0\n", "[dogs.caniche]\ndamage = 1.0\n"] {
+        let text = format!("version = 1\n{}{}", grid, extra);
+        assert!(matches!(parse_level(&text, "t.toml"), Err(LevelError::Invalid { .. })), "{}", extra);
+    }
+}
+
+#[test]
+fn tint_blends_towards_color() {
+    let mut fb = Framebuffer::new(2, 1);
+    fb.set_background_color(Color::new(0, 0, 200, 255));
+    fb.clear();
+    let red = Color::new(255, 0, 0, 255);
+    fb.tint(red, 0.5);
+    let rgba = |c: Color| (c.r, c.g, c.b, c.a);
+    assert_eq!(rgba(fb.pixels()[0]), (127, 0, 100, 255));
+    fb.tint(red, 0.0);
+    assert_eq!(rgba(fb.pixels()[1]), (127, 0, 100, 255));
+}
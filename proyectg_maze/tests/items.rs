@@ -0,0 +1,175 @@
+// tests/items.rs
+// Comida en el mapa, inventario del jugador y perros que dejan de perseguir para comer.
+
+use raylib::prelude::Vector2;
+
+use proyectg_maze::doors::Doors;
+use proyectg_maze::enemy::{AiState, contact_damage, default_dog_stats, spawn_enemies, update_enemies};
+use proyectg_maze::items::{Inventory, Item, ItemKind, THROW_SPEED, launch_item, pick_up_items, update_items};
+use proyectg_maze::level::{LevelError, load_level, parse_level};
+use proyectg_maze::maze::parse_maze;
+use proyectg_maze::sprites::Sprite;
+use proyectg_maze::terrain::Terrain;
+
+const DT: f32 = 1.0 / 30.0;
+
+const ROOM: &str = "\
++-----------+
+|           |
+|           |
++-----------+
+";
+
+fn dog(x: f32, y: f32) -> Sprite {
+    Sprite {
+        pos: Vector2::new(x, y),
+        tex_keys: vec!['c'],
+        current_frame: 0,
+        frame_time: 0.1,
+        timer: 0.0,
+        size: 1.0,
+    }
+}
+
+#[test]
+fn inventory_has_a_capacity_and_a_selection() {
+    let mut inv = Inventory::new();
+    assert_eq!(inv.take_selected(), None);
+    for _ in 0..Inventory::CAPACITY - 1 {
+        assert!(inv.add(ItemKind::Bone));
+    }
+    assert!(inv.add(ItemKind::Treat));
+    assert!(!inv.add(ItemKind::Bone));
+
+    inv.cycle();
+    assert_eq!(inv.selected_kind(), Some(ItemKind::Bone));
+    inv.selected = Inventory::CAPACITY - 1;
+    assert_eq!(inv.take_selected(), Some(ItemKind::Treat));
+    // la seleccion queda dentro del inventario
+    assert_eq!(inv.selected, Inventory::CAPACITY - 2);
+    assert_eq!(inv.items.len(), Inventory::CAPACITY - 1);
+}
+
+#[test]
+fn pickup_needs_reach_and_waits_after_a_drop() {
+    let mut inv = Inventory::new();
+    let mut items = vec![Item::new(ItemKind::Bone, Vector2::new(1.5, 1.5)), Item::new(ItemKind::Treat, Vector2::new(5.5, 1.5))];
+    assert_eq!(pick_up_items(&mut items, Vector2::new(1.7, 1.5), &mut inv), 1);
+    assert_eq!(inv.items, vec![ItemKind::Bone]);
+    assert_eq!(items.len(), 1);
+
+    // recien soltado no se vuelve a juntar
+    let m = parse_maze(ROOM, "test").unwrap();
+    let mut items = vec![launch_item(ItemKind::Bone, Vector2::new(3.5, 1.5), 0.0, 0.0)];
+    assert_eq!(pick_up_items(&mut items, Vector2::new(3.5, 1.5), &mut inv), 0);
+    for _ in 0..40 {
+        update_items(&mut items, &m, &Doors::NONE, &Terrain::FLAT, DT);
+    }
+    assert_eq!(pick_up_items(&mut items, Vector2::new(3.5, 1.5), &mut inv), 1);
+}
+
+#[test]
+fn thrown_items_slow_down_and_stop_at_walls() {
+    let m = parse_maze(ROOM, "test").unwrap();
+    let mut items = vec![launch_item(ItemKind::Treat, Vector2::new(1.5, 1.5), 0.0, THROW_SPEED)];
+    for _ in 0..300 {
+        update_items(&mut items, &m, &Doors::NONE, &Terrain::FLAT, DT);
+    }
+    let pos = items[0].pos();
+    assert!(pos.x > 3.0 && pos.x < 12.0, "{:?}", pos);
+    assert_eq!(items[0].vel, Vector2::zero());
+
+    // contra la pared de la derecha se detiene antes de entrar
+    let mut items = vec![launch_item(ItemKind::Treat, Vector2::new(9.5, 1.5), 0.0, THROW_SPEED)];
+    for _ in 0..300 {
+        update_items(&mut items, &m, &Doors::NONE, &Terrain::FLAT, DT);
+    }
+    assert!(items[0].pos().x < 12.0);
+}
+
+#[test]
+fn hungry_dog_eats_food_instead_of_biting() {
+    let m = parse_maze(ROOM, "test").unwrap();
+    let mut sprites = vec![dog(1.5, 1.5)];
+    let mut enemies = spawn_enemies(&mut sprites, &default_dog_stats(), 1);
+    let player = Vector2::new(8.5, 1.5);
+
+    update_enemies(&mut enemies, &mut sprites, &m, &Doors::NONE, &Terrain::FLAT, player, &mut Vec::new(), DT);
+    assert_eq!(enemies[0].state, AiState::Chase);
+
+    // el jugador tira una galleta entre el perro y el
+    let mut items = vec![Item::new(ItemKind::Treat, Vector2::new(4.5, 2.5))];
+    update_enemies(&mut enemies, &mut sprites, &m, &Doors::NONE, &Terrain::FLAT, player, &mut items, DT);
+    assert_eq!(enemies[0].state, AiState::Fetch);
+    assert_eq!(sprites[0].tex_keys, vec!['c']);
+    for _ in 0..120 {
+        update_enemies(&mut enemies, &mut sprites, &m, &Doors::NONE, &Terrain::FLAT, player, &mut items, DT);
+        if enemies[0].state == AiState::Eating { break; }
+    }
+    assert_eq!(enemies[0].state, AiState::Eating);
+    assert!(items.is_empty());
+
+    // comiendo no se mueve ni muerde, aunque el jugador este encima
+    let eating_at = sprites[0].pos;
+    assert_eq!(contact_damage(&enemies, &sprites, eating_at), None);
+    for _ in 0..60 {
+        update_enemies(&mut enemies, &mut sprites, &m, &Doors::NONE, &Terrain::FLAT, eating_at, &mut items, DT);
+    }
+    assert_eq!(sprites[0].pos, eating_at);
+
+    // al terminar la galleta vuelve a perseguir
+    let feed_frames = (ItemKind::Treat.feed_time() / DT) as usize;
+    for _ in 0..feed_frames {
+        update_enemies(&mut enemies, &mut sprites, &m, &Doors::NONE, &Terrain::FLAT, player, &mut items, DT);
+    }
+    assert_eq!(enemies[0].state, AiState::Chase);
+}
+
+#[test]
+fn dogs_give_up_on_food_they_cannot_reach() {
+    // la galleta esta del otro lado de unas rejas: se ve pero no hay camino
+    let m = parse_maze("+-----------+\n|     #     |\n+-----------+\n", "test").unwrap();
+    let mut sprites = vec![dog(2.5, 1.5)];
+    let mut enemies = spawn_enemies(&mut sprites, &default_dog_stats(), 1);
+    let player = Vector2::new(20.5, 1.5);
+    let mut items = vec![Item::new(ItemKind::Treat, Vector2::new(8.5, 1.5))];
+
+    update_enemies(&mut enemies, &mut sprites, &m, &Doors::NONE, &Terrain::FLAT, player, &mut items, DT);
+    assert_eq!(enemies[0].state, AiState::Return);
+    // y por un rato no le vuelve a hacer caso
+    for _ in 0..30 {
+        update_enemies(&mut enemies, &mut sprites, &m, &Doors::NONE, &Terrain::FLAT, player, &mut items, DT);
+        assert_ne!(enemies[0].state, AiState::Fetch);
+    }
+    assert_eq!(items.len(), 1);
+
+    // ni si el unico paso es una puerta con cerradura
+    let m = parse_maze("+-------+\n|   #   |\n|   R   |\n+-------+\n", "test").unwrap();
+    let doors = Doors::from_maze(&m);
+    let mut sprites = vec![dog(2.5, 1.5)];
+    let mut enemies = spawn_enemies(&mut sprites, &default_dog_stats(), 1);
+    let mut items = vec![Item::new(ItemKind::Treat, Vector2::new(6.5, 1.5))];
+    update_enemies(&mut enemies, &mut sprites, &m, &doors, &Terrain::FLAT, player, &mut items, DT);
+    assert_eq!(enemies[0].state, AiState::Return);
+    // sin la cerradura va a buscarla
+    let mut enemies = spawn_enemies(&mut sprites, &default_dog_stats(), 1);
+    update_enemies(&mut enemies, &mut sprites, &m, &Doors::NONE, &Terrain::FLAT, player, &mut items, DT);
+    assert_eq!(enemies[0].state, AiState::Fetch);
+}
+
+#[test]
+fn levels_place_items_on_free_cells() {
+    let level = load_level("levels/laberinto_verde.toml").unwrap();
+    assert!(level.items.iter().any(|i| i.kind == ItemKind::Bone));
+
+    let grid = "grid = '''\n+---+\n|   g\n+---+\n'''\n";
+    let ok = format!("version = 1\n{}[[items]]\nx = 1.5\ny = 1.5\nkind = \"treat\"\n", grid);
+    let level = parse_level(&ok, "t.toml").unwrap();
+    assert_eq!(level.items[0].kind, ItemKind::Treat);
+    assert_eq!(level.items[0].sprite.tex_keys, vec!['t']);
+
+    for bad in ["x = 0.5\ny = 0.5\nkind = \"bone\"\n", "x = 1.5\ny = 1.5\nkind = \"pelota\"\n"] {
+        let text = format!("version = 1\n{}[[items]]\n{}", grid, bad);
+        assert!(matches!(parse_level(&text, "t.toml"), Err(LevelError::Invalid { .. })), "{}", bad);
+    }
+}
@@ -0,0 +1,95 @@
+// tests/level.rs
+// Formato de nivel TOML y carga de .txt como respaldo.
+
+use proyectg_maze::level::{load_level, parse_level, LevelError};
+use proyectg_maze::maze::{load_maze, MazeError};
+
+const SMALL: &str = r#"
+version = 1
+name = "Sala"
+par_time = 30.0
+grid = '''
++---+
+|   |
+|   g
++---+
+'''
+
+[spawn]
+x = 1.5
+y = 2.5
+angle = 1.0
+
+[textures]
+"g" = "assets/salida.png"
+"k" = "assets/llave.png"
+
+[[entities]]
+x = 2.5
+y = 1.5
+frames = ["m"]
+"#;
+
+#[test]
+fn bundled_level_matches_text_maze() {
+    let level = load_level("levels/laberinto_verde.toml").unwrap();
+    assert_eq!(level.maze, load_maze("maze.txt").unwrap());
+    assert_eq!(level.goal, Some((16, 5)));
+    assert_eq!(level.sprites.len(), 1);
+    assert_eq!(level.sprites[0].tex_keys, vec!['c', 'a']);
+}
+
+#[test]
+fn text_maze_loads_as_level() {
+    let level = load_level("maze.txt").unwrap();
+    assert_eq!(level.name, "maze");
+    assert_eq!((level.spawn.x, level.spawn.y), (1.5, 1.5));
+    assert_eq!(level.goal, Some((16, 5)));
+    assert_eq!(level.sprites.len(), 1);
+}
+
+#[test]
+fn parses_metadata_entities_and_textures() {
+    let level = parse_level(SMALL, "sala.toml").unwrap();
+    assert_eq!(level.name, "Sala");
+    assert_eq!(level.par_time, Some(30.0));
+    assert_eq!((level.spawn.x, level.spawn.y, level.spawn_angle), (1.5, 2.5, 1.0));
+    assert_eq!(level.goal, Some((4, 2)));
+    assert_eq!(level.sprites[0].tex_keys, vec!['m']);
+    assert_eq!(level.sprites[0].frame_time, 0.1);
+
+    let path = |ch| level.textures.iter().find(|(c, _)| *c == ch).map(|(_, p)| p.as_str());
+    assert_eq!(path('g'), Some("assets/salida.png"));
+    assert_eq!(path('k'), Some("assets/llave.png"));
+    assert_eq!(path('-'), Some("assets/wall2.png"));
+}
+
+#[test]
+fn rejects_unsupported_version() {
+    let text = SMALL.replace("version = 1", "version = 7");
+    assert!(matches!(
+        parse_level(&text, "sala.toml"),
+        Err(LevelError::UnsupportedVersion { version: 7, .. })
+    ));
+}
+
+#[test]
+fn rejects_spawn_inside_wall() {
+    let text = SMALL.replace("x = 1.5\ny = 2.5", "x = 0.5\ny = 0.5");
+    assert!(matches!(parse_level(&text, "sala.toml"), Err(LevelError::Invalid { .. })));
+}
+
+#[test]
+fn reports_grid_errors_and_typos() {
+    let text = SMALL.replace("|   |", "|  x|");
+    match parse_level(&text, "sala.toml") {
+        Err(LevelError::Maze(MazeError::Parse { file, issues })) => {
+            assert_eq!(file, "sala.toml [grid]");
+            assert_eq!((issues[0].line, issues[0].column), (2, 4));
+        }
+        _ => panic!("se esperaba un error en la grilla"),
+    }
+
+    let text = SMALL.replace("par_time", "partime");
+    assert!(matches!(parse_level(&text, "sala.toml"), Err(LevelError::Syntax { .. })));
+}
@@ -0,0 +1,50 @@
+// tests/level_completion.rs
+// La salida 'g' se puede atravesar y las estadisticas del nivel se acumulan bien.
+
+use raylib::prelude::Vector2;
+
+use proyectg_maze::controller::{cell_is_free, on_goal};
+use proyectg_maze::doors::Doors;
+use proyectg_maze::maze::load_maze;
+use proyectg_maze::pathfinding::{distance_field, find_path};
+use proyectg_maze::stats::{format_time, LevelStats};
+use proyectg_maze::validate::validate_maze;
+use proyectg_maze::terrain::Terrain;
+
+#[test]
+fn exit_is_walkable_but_still_rendered_as_wall() {
+    let maze = load_maze("maze.txt").unwrap();
+    assert_eq!(maze[5][16], 'g');
+    assert!(cell_is_free(&maze, &Doors::NONE, 16, 5));
+    assert!(on_goal(&maze, Vector2::new(16.2, 5.5)));
+    assert!(!on_goal(&maze, Vector2::new(15.5, 5.5)));
+    assert!(!on_goal(&maze, Vector2::new(-1.0, 5.5)));
+    // sigue cerrado para los rayos: el validador no ve bordes abiertos
+    assert!(validate_maze(&maze, &Terrain::FLAT).is_valid());
+}
+
+#[test]
+fn exit_can_be_reached_by_walking() {
+    let maze = load_maze("maze.txt").unwrap();
+    let path = find_path(&maze, &Doors::NONE, &Terrain::FLAT, (1, 1), (16, 5)).unwrap();
+    assert_eq!(path.last(), Some(&(16, 5)));
+    assert_eq!(distance_field(&maze, &Doors::NONE, &Terrain::FLAT, &[(1, 1)]).get((16, 5)), Some(path.len() as u32 - 1));
+}
+
+#[test]
+fn stats_count_time_steps_and_distance() {
+    let mut stats = LevelStats::new();
+    stats.record(Vector2::new(1.5, 1.5), Vector2::new(1.9, 1.5), 0.5);
+    stats.record(Vector2::new(1.9, 1.5), Vector2::new(2.2, 1.5), 0.5);
+    stats.record(Vector2::new(2.2, 1.5), Vector2::new(2.2, 1.5), 0.25);
+    assert_eq!(stats.steps, 1);
+    assert!((stats.distance - 0.7).abs() < 1e-5);
+    assert!((stats.elapsed - 1.25).abs() < 1e-6);
+}
+
+#[test]
+fn formats_elapsed_time() {
+    assert_eq!(format_time(0.0), "0:00.0");
+    assert_eq!(format_time(9.94), "0:09.9");
+    assert_eq!(format_time(75.25), "1:15.3");
+}
@@ -0,0 +1,53 @@
+// tests/maze_loading.rs
+// Carga de laberintos: errores con archivo, linea y columna.
+
+use proyectg_maze::maze::{load_maze, parse_maze, IssueKind, MazeError, ParseIssue};
+
+#[test]
+fn loads_bundled_maze() {
+    let maze = load_maze("maze.txt").unwrap();
+    assert_eq!(maze.len(), 13);
+    assert!(maze.iter().all(|row| row.len() == 17));
+}
+
+#[test]
+fn missing_file_is_io_error() {
+    match load_maze("no_existe.txt") {
+        Err(MazeError::Io { file, .. }) => assert_eq!(file, "no_existe.txt"),
+        other => panic!("se esperaba Io, llego {:?}", other),
+    }
+}
+
+#[test]
+fn empty_file_is_rejected() {
+    assert!(matches!(parse_maze("", "vacio.txt"), Err(MazeError::Empty { .. })));
+    assert!(matches!(parse_maze("\n\n", "vacio.txt"), Err(MazeError::Empty { .. })));
+}
+
+#[test]
+fn accepts_crlf_and_trailing_newlines() {
+    let maze = parse_maze("+-+\r\n| g\r\n+-+\r\n\n", "crlf.txt").unwrap();
+    assert_eq!(maze, vec![vec!['+', '-', '+'], vec!['|', ' ', 'g'], vec!['+', '-', '+']]);
+}
+
+#[test]
+fn reports_every_problem_at_once() {
+    let text = "+--+\n|x |\n| +\n+-?-+\n";
+    let Err(MazeError::Parse { file, issues }) = parse_maze(text, "malo.txt") else {
+        panic!("se esperaba un error de formato");
+    };
+    assert_eq!(file, "malo.txt");
+    assert_eq!(issues, vec![
+        ParseIssue { line: 2, column: 2, kind: IssueKind::UnknownSymbol('x') },
+        ParseIssue { line: 3, column: 4, kind: IssueKind::RaggedRow { expected: 4, found: 3 } },
+        ParseIssue { line: 4, column: 3, kind: IssueKind::UnknownSymbol('?') },
+        ParseIssue { line: 4, column: 5, kind: IssueKind::RaggedRow { expected: 4, found: 5 } },
+    ]);
+}
+
+#[test]
+fn error_message_names_file_line_and_column() {
+    let err = parse_maze("++\n+x\n", "nivel.txt").unwrap_err();
+    let msg = err.to_string();
+    assert!(msg.contains("nivel.txt:linea 2, columna 2"), "{}", msg);
+}
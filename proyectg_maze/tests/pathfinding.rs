@@ -0,0 +1,74 @@
+// tests/pathfinding.rs
+// Campos de distancia, A* y cache hacia la salida.
+
+use proyectg_maze::maze::generate::{generate_with, Algorithm, GenOptions};
+use proyectg_maze::doors::Doors;
+use proyectg_maze::maze::{load_maze, parse_maze, Maze};
+use proyectg_maze::pathfinding::{distance_field, find_path, goal_cells, PathCache};
+use proyectg_maze::terrain::Terrain;
+
+fn maze(text: &str) -> Maze {
+    parse_maze(text, "test").unwrap()
+}
+
+const SMALL: &str = "+-----+\n|     |\n| +-+ |\n| |   g\n+-+---+\n";
+
+#[test]
+fn distance_field_counts_steps_around_walls() {
+    let m = maze(SMALL);
+    let field = distance_field(&m, &Doors::NONE, &Terrain::FLAT, &[(1, 1)]);
+    assert_eq!(field.get((1, 1)), Some(0));
+    assert_eq!(field.get((5, 1)), Some(4));
+    assert_eq!(field.get((3, 3)), Some(8));
+    assert_eq!(field.get((1, 3)), Some(2));
+    // paredes y celdas fuera del mapa no tienen distancia
+    assert_eq!(field.get((2, 2)), None);
+    assert_eq!(field.get((40, 1)), None);
+}
+
+#[test]
+fn goal_field_reaches_exit_wall() {
+    let m = maze(SMALL);
+    assert_eq!(goal_cells(&m), vec![(6, 3)]);
+    let mut cache = PathCache::new();
+    let path = cache.to_goal(&m, &Doors::NONE, &Terrain::FLAT).path_from(&m, &Doors::NONE, &Terrain::FLAT, (1, 1)).unwrap();
+    assert_eq!(path.first(), Some(&(1, 1)));
+    assert_eq!(path.last(), Some(&(6, 3)));
+    assert_eq!(path.len(), 8);
+}
+
+#[test]
+fn a_star_matches_bfs_length() {
+    let options = GenOptions { braid: 0.4, rooms: 2, ..Default::default() };
+    for algorithm in Algorithm::ALL {
+        let m = generate_with(12, 8, algorithm, 17, &options);
+        let field = distance_field(&m, &Doors::NONE, &Terrain::FLAT, &[(1, 1)]);
+        for goal in [(23, 15), (11, 7), (1, 15), (23, 1)] {
+            let path = find_path(&m, &Doors::NONE, &Terrain::FLAT, (1, 1), goal).unwrap();
+            assert_eq!(path.len() as u32 - 1, field.get(goal).unwrap(), "{:?} {:?}", algorithm, goal);
+            // cada paso es a una vecina ortogonal
+            for pair in path.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                assert_eq!(a.0.abs_diff(b.0) + a.1.abs_diff(b.1), 1);
+            }
+        }
+    }
+}
+
+#[test]
+fn a_star_rejects_walls_and_unreachable_cells() {
+    let m = maze("+-----+\n| | | |\n+-----+\n");
+    assert_eq!(find_path(&m, &Doors::NONE, &Terrain::FLAT, (1, 1), (3, 1)), None);
+    assert_eq!(find_path(&m, &Doors::NONE, &Terrain::FLAT, (1, 1), (2, 1)), None);
+    assert_eq!(find_path(&m, &Doors::NONE, &Terrain::FLAT, (1, 1), (1, 1)), Some(vec![(1, 1)]));
+}
+
+#[test]
+fn bundled_maze_path_to_exit() {
+    let m = load_maze("maze.txt").unwrap();
+    let mut cache = PathCache::new();
+    let first = cache.to_goal(&m, &Doors::NONE, &Terrain::FLAT).get((1, 1));
+    assert!(first.is_some());
+    // segunda consulta: mismo campo desde el cache
+    assert_eq!(cache.to_goal(&m, &Doors::NONE, &Terrain::FLAT).get((1, 1)), first);
+}
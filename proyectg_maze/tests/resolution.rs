@@ -0,0 +1,58 @@
+// tests/resolution.rs
+// Resolucion interna: lectura de la opcion --res y escalado dinamico.
+
+use proyectg_maze::resolution::{RenderResolution, ResolutionScaler, parse_resolution};
+
+const DT: f32 = 1.0 / 60.0;
+
+#[test]
+fn parses_fixed_sizes_and_scales() {
+    assert_eq!(parse_resolution("320x200"), Some(RenderResolution::Fixed(320, 200)));
+    assert_eq!(parse_resolution(" 640 x 480 "), Some(RenderResolution::Fixed(640, 480)));
+    assert_eq!(parse_resolution("0.5"), Some(RenderResolution::Scale(0.5)));
+    assert_eq!(parse_resolution("50%"), Some(RenderResolution::Scale(0.5)));
+}
+
+#[test]
+fn rejects_malformed_resolutions() {
+    for bad in ["0x0", "640x0", "640", "axb", "640x480x2", "", "0", "-1", "500%"] {
+        assert_eq!(parse_resolution(bad), None, "{:?}", bad);
+    }
+}
+
+#[test]
+fn without_budget_the_size_is_the_requested_one() {
+    let mut scaler = ResolutionScaler::new(RenderResolution::Fixed(320, 200));
+    scaler.update(1.0, 10.0);
+    assert_eq!(scaler.target_size(1280, 720), (320, 200));
+    let scaler = ResolutionScaler::new(RenderResolution::Scale(0.001));
+    assert_eq!(scaler.target_size(640, 480), (1, 1));
+}
+
+#[test]
+fn dynamic_scale_converges_within_its_bounds() {
+    let mut scaler = ResolutionScaler::new(RenderResolution::Scale(1.0)).with_dynamic(0.016);
+    assert_eq!(scaler.target_size(640, 480), (640, 480));
+
+    // un pico aislado antes del intervalo no cambia nada
+    scaler.update(0.1, DT);
+    assert_eq!(scaler.target_size(640, 480), (640, 480));
+
+    // frames caros: baja hasta un cuarto de la resolucion y no pasa de ahi
+    for _ in 0..600 {
+        scaler.update(0.05, DT);
+    }
+    assert_eq!(scaler.target_size(640, 480), (160, 120));
+
+    // dentro del presupuesto se queda donde esta
+    for _ in 0..600 {
+        scaler.update(0.012, DT);
+    }
+    assert_eq!(scaler.target_size(640, 480), (160, 120));
+
+    // frames baratos: vuelve a la resolucion elegida sin pasarse
+    for _ in 0..1200 {
+        scaler.update(0.001, DT);
+    }
+    assert_eq!(scaler.target_size(640, 480), (640, 480));
+}
@@ -0,0 +1,127 @@
+// tests/see_through.rs
+// Paredes con transparencia: varios impactos por rayo, mezcla por alfa y oclusion
+// de sprites detras de rejas.
+
+use raylib::prelude::*;
+
+use proyectg_maze::camera::{Camera, DEFAULT_FOV};
+use proyectg_maze::caster::{cast_ray_dir, cast_ray_layers};
+use proyectg_maze::doors::Doors;
+use proyectg_maze::enemy::can_see;
+use proyectg_maze::framebuffer::{Framebuffer, blend};
+use proyectg_maze::headless::render_frame;
+use proyectg_maze::maze::{Maze, parse_maze};
+use proyectg_maze::player::Player;
+use proyectg_maze::render3d::render3d;
+use proyectg_maze::sprites::Sprite;
+use proyectg_maze::terrain::Terrain;
+use proyectg_maze::textures::TextureManager;
+
+fn maze(text: &str) -> Maze {
+    parse_maze(text, "test").unwrap()
+}
+
+fn camera(pos: Vector2) -> Camera {
+    let mut camera = Camera::new(DEFAULT_FOV, 80, 60);
+    camera.follow(&Player::new(pos, 0.0));
+    camera
+}
+
+fn rgb(c: &Color) -> (u8, u8, u8) {
+    (c.r, c.g, c.b)
+}
+
+const RIGHT: Vector2 = Vector2 { x: 1.0, y: 0.0 };
+
+#[test]
+fn rays_collect_see_through_walls_until_an_opaque_one() {
+    let m = maze("+---------+\n|  ##  H  |\n+---------+\n");
+    let origin = Vector2::new(1.5, 1.5);
+
+    let hits = cast_ray_layers(&m, &Doors::NONE, &Terrain::FLAT, origin, RIGHT);
+    // dos celdas de rejas seguidas cuentan una sola vez
+    let impacts: Vec<char> = hits.iter().map(|h| h.impact).collect();
+    assert_eq!(impacts, vec!['#', 'H', '|']);
+    assert!((hits[0].distance - 1.5).abs() < 1e-4);
+    assert!((hits[1].distance - 5.5).abs() < 1e-4);
+
+    // el primer impacto sigue siendo el de cast_ray_dir
+    assert_eq!(cast_ray_dir(&m, &Doors::NONE, origin, RIGHT).unwrap().impact, '#');
+
+    // los perros ven a traves del seto pero no de la pared
+    assert!(can_see(&m, &Doors::NONE, origin, Vector2::new(8.5, 1.5), 10.0));
+    assert!(!can_see(&m, &Doors::NONE, origin, Vector2::new(1.5, 0.5), 10.0));
+}
+
+#[test]
+fn blending_follows_the_source_alpha() {
+    let dst = Color::new(0, 0, 200, 255);
+    assert_eq!(rgb(&blend(dst, Color::new(255, 0, 0, 0))), (0, 0, 200));
+    assert_eq!(rgb(&blend(dst, Color::new(255, 0, 0, 255))), (255, 0, 0));
+    let half = blend(dst, Color::new(200, 0, 0, 128));
+    assert!(half.r.abs_diff(100) <= 1 && half.b.abs_diff(100) <= 1, "{:?}", half);
+    assert_eq!(half.a, 255);
+}
+
+#[test]
+fn the_wall_behind_shows_through_the_gaps() {
+    let tex = TextureManager::load_headless();
+    let cam = camera(Vector2::new(1.5, 2.5));
+    let open = maze("+------+\n|      |\n|      |\n|      |\n+------+\n");
+    let barred = maze("+------+\n|      |\n|  #   |\n|      |\n+------+\n");
+
+    let plain = render_frame(&open, &Doors::NONE, &Terrain::FLAT, &cam, &[], &tex, 1);
+    let bars = render_frame(&barred, &Doors::NONE, &Terrain::FLAT, &cam, &[], &tex, 1);
+    let (same, different) = plain
+        .pixels()
+        .iter()
+        .zip(bars.pixels())
+        .fold((0, 0), |(s, d), (a, b)| if rgb(a) == rgb(b) { (s + 1, d) } else { (s, d + 1) });
+    assert!(different > 0, "las rejas no se dibujaron");
+    // entre los barrotes se sigue viendo la pared del fondo (y el cielo y el piso)
+    assert!(same > plain.pixels().len() / 2, "{} iguales", same);
+
+    // el z-buffer guarda la pared opaca y el tramo de rejas delante
+    let mut fb = Framebuffer::new(cam.width, cam.height);
+    let zbuf = render3d(&mut fb, &barred, &Doors::NONE, &Terrain::FLAT, &cam, &tex, 1);
+    let mid = cam.width as usize / 2;
+    let wall = zbuf.covers[mid].last().unwrap();
+    assert!((wall.depth - 5.5).abs() < 0.1, "{}", wall.depth);
+    assert_eq!(zbuf.layers[mid].len(), 1);
+    assert!((zbuf.layers[mid][0].depth - 1.5).abs() < 0.1);
+}
+
+#[test]
+fn sprites_behind_bars_are_covered_by_them() {
+    let tex = TextureManager::load_headless();
+    let cam = camera(Vector2::new(1.5, 2.5));
+    let open = maze("+------+\n|      |\n|      |\n|      |\n+------+\n");
+    let barred = maze("+------+\n|      |\n|  #   |\n|      |\n+------+\n");
+    let dog = [Sprite {
+        pos: Vector2::new(4.5, 2.5),
+        tex_keys: vec!['m'],
+        current_frame: 0,
+        frame_time: 0.1,
+        timer: 0.0,
+        size: 1.0,
+    }];
+
+    // detras de las rejas el perro se ve entre los barrotes, no encima de ellos
+    let behind = render_frame(&barred, &Doors::NONE, &Terrain::FLAT, &cam, &dog, &tex, 1);
+    let bars = render_frame(&barred, &Doors::NONE, &Terrain::FLAT, &cam, &[], &tex, 1);
+    let clear = render_frame(&open, &Doors::NONE, &Terrain::FLAT, &cam, &dog, &tex, 1);
+    let mut on_bars = 0;
+    let mut through_gaps = 0;
+    for ((b, n), c) in behind.pixels().iter().zip(bars.pixels()).zip(clear.pixels()) {
+        // el pixel de la reja quedo igual que sin perro
+        if rgb(b) == rgb(n) && rgb(b) != rgb(c) {
+            on_bars += 1;
+        }
+        // el pixel del perro quedo igual que sin reja
+        if rgb(b) == rgb(c) && rgb(b) != rgb(n) {
+            through_gaps += 1;
+        }
+    }
+    assert!(on_bars > 0, "las rejas no tapan al perro");
+    assert!(through_gaps > 0, "el perro no se ve entre las rejas");
+}
@@ -0,0 +1,146 @@
+// tests/terrain.rs
+// Alturas por celda: paredes bajas y altas, pisos elevados, escalones y su render.
+
+use raylib::prelude::*;
+
+use proyectg_maze::camera::{Camera, DEFAULT_FOV};
+use proyectg_maze::caster::{cast_ray_dir, cast_ray_layers};
+use proyectg_maze::doors::Doors;
+use proyectg_maze::framebuffer::Framebuffer;
+use proyectg_maze::level::{LevelError, parse_level};
+use proyectg_maze::maze::{Maze, parse_maze};
+use proyectg_maze::pathfinding::{distance_field, find_path, reach_field};
+use proyectg_maze::player::Player;
+use proyectg_maze::render3d::render3d;
+use proyectg_maze::terrain::Terrain;
+use proyectg_maze::textures::TextureManager;
+use proyectg_maze::validate::{ValidationIssue, validate_maze};
+
+fn maze(text: &str) -> Maze {
+    parse_maze(text, "test").unwrap()
+}
+
+const RIGHT: Vector2 = Vector2 { x: 1.0, y: 0.0 };
+
+// Sala con una pared suelta en (3, 2)
+const ROOM: &str = "+------+\n|      |\n|  -   |\n|      |\n+------+\n";
+
+#[test]
+fn heights_grid_sets_walls_and_floors() {
+    let m = maze(ROOM);
+    let err = Terrain::from_grid(&m, "........5\n").unwrap_err();
+    assert!(err.contains("(8, 0)"), "{}", err);
+
+    let t = Terrain::from_grid(&m, "8.......\n........\n.1.2..3.\n").unwrap();
+    assert_eq!(t.wall_height((0, 0)), 2.0);
+    assert_eq!(t.wall_height((3, 2)), 0.5);
+    assert_eq!(t.wall_height((1, 0)), 1.0);
+    assert_eq!(t.floor_height((1, 2)), 0.25);
+    assert_eq!(t.floor_height((6, 2)), 0.75);
+    assert_eq!(t.floor_at(Vector2::new(6.5, 2.5)), 0.75);
+    assert_eq!(t.floor_height((2, 2)), 0.0);
+
+    assert!(Terrain::from_grid(&m, "x").is_err());
+    assert!(Terrain::from_grid(&m, "0").is_err());
+
+    // en el nivel un error en las alturas es un nivel invalido
+    let level = |heights: &str| {
+        format!("version = 1\ngrid = '''\n+---+\n|   |\n+---+\n'''\nheights = '''\n{}\n'''\n", heights)
+    };
+    assert!(matches!(parse_level(&level("...\n.x"), "t.toml"), Err(LevelError::Invalid { .. })));
+    let l = parse_level(&level("\n.2"), "t.toml").unwrap();
+    assert_eq!(l.terrain.floor_height((1, 1)), 0.5);
+}
+
+#[test]
+fn the_player_climbs_low_steps_only() {
+    let m = maze("+-----+\n|     |\n+-----+\n");
+    let t = Terrain::from_grid(&m, "\n..24.").unwrap();
+    let at = |x: f32| Vector2::new(x, 1.5);
+    assert!(t.can_climb(at(1.5), at(2.5)));
+    assert!(t.can_climb(at(2.5), at(3.5)));
+    assert!(!t.can_climb(at(1.5), at(3.5)));
+    // bajar siempre se puede
+    assert!(t.can_climb(at(3.5), at(1.5)));
+}
+
+#[test]
+fn dogs_and_the_validator_respect_tall_steps() {
+    let m = maze("+-----+\n|    g|\n+-----+\n");
+    // la salida esta sobre una plataforma de 0.75 sin escalon intermedio
+    let t = Terrain::from_grid(&m, "\n...33").unwrap();
+    let report = validate_maze(&m, &t);
+    assert!(report.issues.contains(&ValidationIssue::GoalUnreachable { goal: (5, 1) }), "{:?}", report.issues);
+    // y la plataforma queda como bolsillo inalcanzable
+    assert!(report.issues.contains(&ValidationIssue::UnreachablePocket { cell: (3, 1), size: 3 }));
+    assert!(validate_maze(&m, &Terrain::FLAT).is_valid());
+
+    // se baja de la plataforma pero no se sube
+    assert_eq!(find_path(&m, &Doors::NONE, &t, (1, 1), (4, 1)), None);
+    assert_eq!(find_path(&m, &Doors::NONE, &t, (4, 1), (1, 1)).unwrap().len(), 4);
+    assert!(!distance_field(&m, &Doors::NONE, &t, &[(4, 1)]).reaches((1, 1)));
+    assert!(reach_field(&m, &Doors::NONE, &t, &[(4, 1)]).reaches((1, 1)));
+
+    // con un escalon en el medio ya se llega
+    let stairs = Terrain::from_grid(&m, "\n..133").unwrap();
+    assert!(validate_maze(&m, &stairs).is_valid());
+}
+
+#[test]
+fn rays_continue_past_low_walls_and_report_raised_floors() {
+    let m = maze(ROOM);
+    let origin = Vector2::new(1.5, 2.5);
+    let mut t = Terrain::FLAT;
+    t.set_wall_height((3, 2), 0.5);
+    t.set_floor_height((5, 2), 0.25);
+
+    let hits = cast_ray_layers(&m, &Doors::NONE, &t, origin, RIGHT);
+    let seen: Vec<(char, f32)> = hits.iter().map(|h| (h.impact, h.height)).collect();
+    assert_eq!(seen, vec![('-', 0.5), (' ', 0.25), ('|', 1.0)]);
+    assert!((hits[1].exit - 4.5).abs() < 1e-4, "{}", hits[1].exit);
+
+    // sin alturas la pared suelta detiene el rayo
+    let flat = cast_ray_layers(&m, &Doors::NONE, &Terrain::FLAT, origin, RIGHT);
+    assert_eq!(flat.len(), 1);
+    assert_eq!(cast_ray_dir(&m, &Doors::NONE, origin, RIGHT).unwrap().impact, '-');
+
+    // una torre tapa todo lo de atras aunque haya paredes mas bajas
+    t.set_wall_height((3, 2), 2.0);
+    assert_eq!(cast_ray_layers(&m, &Doors::NONE, &t, origin, RIGHT).len(), 1);
+}
+
+#[test]
+fn taller_walls_show_above_lower_ones() {
+    let tex = TextureManager::load_headless();
+    let m = maze(ROOM);
+    let mut t = Terrain::FLAT;
+    t.set_wall_height((3, 2), 0.5);
+
+    let mut camera = Camera::new(DEFAULT_FOV, 80, 60);
+    camera.follow(&Player::new(Vector2::new(1.5, 2.5), 0.0));
+    let mid = camera.width as usize / 2;
+    let mut fb = Framebuffer::new(camera.width, camera.height);
+
+    // a la altura de los ojos la pared baja llega al horizonte y atras se ve la alta
+    let zbuf = render3d(&mut fb, &m, &Doors::NONE, &t, &camera, &tex, 1);
+    let covers = &zbuf.covers[mid];
+    assert_eq!(covers.len(), 2);
+    assert_eq!(covers[0].top, camera.horizon() as usize);
+    assert!(covers[1].top < covers[0].top && covers[1].depth > covers[0].depth);
+    let above = fb.pixels()[(covers[0].top - 2) * camera.width as usize + mid];
+    assert_ne!((above.r, above.g, above.b), (Color::SKYBLUE.r, Color::SKYBLUE.g, Color::SKYBLUE.b));
+
+    // un sprite detras de la pared baja solo se ve por encima de ella
+    assert_eq!(zbuf.visible_rows(mid, 1.0), usize::MAX);
+    assert_eq!(zbuf.visible_rows(mid, 3.0), covers[0].top);
+    assert_eq!(zbuf.visible_rows(mid, 9.0), covers[1].top);
+
+    // desde una plataforma se ve la cara de arriba de la pared baja
+    camera.z = 0.5;
+    let zbuf = render3d(&mut fb, &m, &Doors::NONE, &t, &camera, &tex, 1);
+    assert_eq!(zbuf.covers[mid].len(), 3);
+
+    // sin alturas es una pared comun
+    let zbuf = render3d(&mut fb, &m, &Doors::NONE, &Terrain::FLAT, &camera, &tex, 1);
+    assert_eq!(zbuf.covers[mid].len(), 1);
+}
@@ -0,0 +1,100 @@
+// tests/validate.rs
+// Validacion de laberintos: cerramiento, spawn, salida y bolsillos.
+
+use proyectg_maze::level::parse_level;
+use proyectg_maze::maze::{load_maze, parse_maze, Maze};
+use proyectg_maze::validate::{validate_level, validate_maze, ValidationIssue};
+use proyectg_maze::terrain::Terrain;
+
+fn maze(text: &str) -> Maze {
+    parse_maze(text, "test").unwrap()
+}
+
+#[test]
+fn bundled_maze_is_valid() {
+    let report = validate_maze(&load_maze("maze.txt").unwrap(), &Terrain::FLAT);
+    assert!(report.is_valid(), "{:?}", report.issues);
+    assert_eq!(report.spawn, Some((1, 1)));
+}
+
+#[test]
+fn detects_open_border() {
+    let report = validate_maze(&maze("+--+\n|  g\n|   \n+--+\n"), &Terrain::FLAT);
+    assert_eq!(report.issues, vec![ValidationIssue::OpenBorder { cell: (3, 2) }]);
+    assert!(!report.is_valid());
+}
+
+#[test]
+fn see_through_and_thin_walls_do_not_enclose() {
+    // las rejas dejan pasar los rayos igual que una celda libre
+    let report = validate_maze(&maze("+-###-+\n|    g|\n+-----+\n"), &Terrain::FLAT);
+    assert_eq!(report.issues, vec![
+        ValidationIssue::OpenBorder { cell: (2, 0) },
+        ValidationIssue::OpenBorder { cell: (3, 0) },
+        ValidationIssue::OpenBorder { cell: (4, 0) },
+    ]);
+    assert!(!report.is_valid());
+
+    // una pared de borde, un pilar o una pared baja en el borde tampoco cierran
+    let report = validate_maze(&maze("+-~I--+\n|    g|\n+-----+\n"), &Terrain::FLAT);
+    assert_eq!(report.issues, vec![
+        ValidationIssue::OpenBorder { cell: (2, 0) },
+        ValidationIssue::OpenBorder { cell: (3, 0) },
+    ]);
+    let m = maze("+-----+\n|    g|\n+-----+\n");
+    let mut low = Terrain::FLAT;
+    low.set_wall_height((0, 1), 0.5);
+    assert_eq!(validate_maze(&m, &low).issues, vec![ValidationIssue::OpenBorder { cell: (0, 1) }]);
+}
+
+#[test]
+fn detects_missing_spawn_and_goal() {
+    let report = validate_maze(&maze("+++\n+++\n"), &Terrain::FLAT);
+    assert_eq!(report.issues, vec![ValidationIssue::NoSpawn, ValidationIssue::NoGoal]);
+}
+
+#[test]
+fn detects_unreachable_goal() {
+    let report = validate_maze(&maze("+---+\n| | g\n+---+\n"), &Terrain::FLAT);
+    assert!(report.issues.contains(&ValidationIssue::GoalUnreachable { goal: (4, 1) }));
+    assert!(!report.is_valid());
+}
+
+#[test]
+fn pockets_are_warnings() {
+    let report = validate_maze(&maze("+-g---+\n|  |  |\n+--+--+\n|   | |\n+-----+\n"), &Terrain::FLAT);
+    assert_eq!(report.issues, vec![
+        ValidationIssue::UnreachablePocket { cell: (4, 1), size: 2 },
+        ValidationIssue::UnreachablePocket { cell: (1, 3), size: 3 },
+        ValidationIssue::UnreachablePocket { cell: (5, 3), size: 1 },
+    ]);
+    assert!(report.is_valid());
+}
+
+#[test]
+fn levels_are_checked_from_their_own_spawn() {
+    // la primera celda libre llega a la salida, pero el spawn esta encerrado
+    let grid = "grid = '''\n+-----+\n|  g| |\n+-----+\n'''\n";
+    let level = parse_level(&format!("version = 1\n{}[spawn]\nx = 5.5\ny = 1.5\n", grid), "t.toml").unwrap();
+    assert!(validate_maze(&level.maze, &level.terrain).is_valid());
+
+    let report = validate_level(&level);
+    assert_eq!(report.spawn, Some((5, 1)));
+    assert!(report.issues.contains(&ValidationIssue::GoalUnreachable { goal: (3, 1) }), "{:?}", report.issues);
+    assert!(!report.is_valid());
+}
+
+#[test]
+fn locked_doors_need_a_reachable_key() {
+    // sin llaves la cerradura corta el camino a la salida
+    let grid = "+-------+\n|   R   g\n+-------+\n";
+    let report = validate_maze(&maze(grid), &Terrain::FLAT);
+    assert!(report.issues.contains(&ValidationIssue::GoalUnreachable { goal: (8, 1) }), "{:?}", report.issues);
+    // lo de atras de la puerta (con la salida) queda como bolsillo
+    assert!(report.issues.contains(&ValidationIssue::UnreachablePocket { cell: (5, 1), size: 4 }), "{:?}", report.issues);
+
+    // con la llave del lado del spawn se llega
+    let text = format!("version = 1\ngrid = '''\n{}'''\n[[items]]\nx = 2.5\ny = 1.5\nkind = \"red_key\"\n", grid);
+    let report = validate_level(&parse_level(&text, "t.toml").unwrap());
+    assert_eq!(report.issues, vec![]);
+}
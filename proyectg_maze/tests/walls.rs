@@ -0,0 +1,89 @@
+// tests/walls.rs
+// Paredes finas: de borde, paneles a mitad de celda y pilares.
+
+use raylib::prelude::Vector2;
+
+use proyectg_maze::caster::{Face, cast_ray_dir};
+use proyectg_maze::controller::{can_step, cell_is_free};
+use proyectg_maze::doors::Doors;
+use proyectg_maze::maze::{Maze, parse_maze};
+use proyectg_maze::pathfinding::find_path;
+use proyectg_maze::walls::{PILLAR_HALF, WallShape, wall_shape};
+use proyectg_maze::terrain::Terrain;
+
+fn maze(text: &str) -> Maze {
+    parse_maze(text, "test").unwrap()
+}
+
+const UP: Vector2 = Vector2 { x: 0.0, y: -1.0 };
+const RIGHT: Vector2 = Vector2 { x: 1.0, y: 0.0 };
+
+#[test]
+fn edge_walls_block_one_side_of_a_walkable_cell() {
+    let m = maze("+-----+\n|  _  |\n|     |\n+-----+\n");
+    assert_eq!(wall_shape(&m, (3, 1), '_'), WallShape::Edge(Face::South));
+    assert!(cell_is_free(&m, &Doors::NONE, 3, 1));
+    assert!(can_step(&m, &Doors::NONE, &Terrain::FLAT, (2, 1), (3, 1)));
+    assert!(!can_step(&m, &Doors::NONE, &Terrain::FLAT, (3, 2), (3, 1)));
+    assert!(!can_step(&m, &Doors::NONE, &Terrain::FLAT, (3, 1), (3, 2)));
+
+    // el camino rodea el borde
+    assert_eq!(find_path(&m, &Doors::NONE, &Terrain::FLAT, (3, 2), (3, 1)).unwrap().len(), 4);
+
+    // desde abajo se golpea el borde; desde adentro de la celda se ve la pared de arriba
+    let hit = cast_ray_dir(&m, &Doors::NONE, Vector2::new(3.5, 2.5), UP).unwrap();
+    assert_eq!((hit.impact, hit.face), ('_', Face::South));
+    assert!((hit.distance - 0.5).abs() < 1e-4, "{}", hit.distance);
+    let hit = cast_ray_dir(&m, &Doors::NONE, Vector2::new(3.5, 1.5), UP).unwrap();
+    assert_eq!(hit.impact, '-');
+    assert!((hit.distance - 0.5).abs() < 1e-4, "{}", hit.distance);
+}
+
+#[test]
+fn panels_are_hit_in_the_middle_of_the_cell() {
+    let m = maze("+---+---+\n|   F   |\n+---+---+\n");
+    assert_eq!(wall_shape(&m, (4, 1), 'F'), WallShape::Panel { along_x: false });
+    assert!(!cell_is_free(&m, &Doors::NONE, 4, 1));
+
+    let hit = cast_ray_dir(&m, &Doors::NONE, Vector2::new(1.5, 1.25), RIGHT).unwrap();
+    assert_eq!((hit.impact, hit.face, hit.cell_x), ('F', Face::West, 4));
+    assert!((hit.distance - 3.0).abs() < 1e-4, "{}", hit.distance);
+    assert!((hit.tex_u - 0.25).abs() < 1e-4, "{}", hit.tex_u);
+}
+
+#[test]
+fn pillars_are_hit_on_their_faces_and_can_be_passed() {
+    let m = maze("+-----+\n|     |\n|  I  |\n|     |\n+-----+\n");
+    let center = Vector2::new(3.5, 2.5);
+
+    let hit = cast_ray_dir(&m, &Doors::NONE, Vector2::new(1.5, 2.6), RIGHT).unwrap();
+    assert_eq!((hit.impact, hit.face), ('I', Face::West));
+    assert!((hit.hit_x - (center.x - PILLAR_HALF)).abs() < 1e-4);
+    // u recorre la cara del pilar, no la celda entera
+    assert!((hit.tex_u - 0.75).abs() < 1e-4, "{}", hit.tex_u);
+
+    let hit = cast_ray_dir(&m, &Doors::NONE, Vector2::new(3.5, 3.5), UP).unwrap();
+    assert_eq!((hit.impact, hit.face), ('I', Face::South));
+
+    // al costado del pilar el rayo sigue hasta la pared
+    let hit = cast_ray_dir(&m, &Doors::NONE, Vector2::new(1.5, 2.8), RIGHT).unwrap();
+    assert_eq!(hit.impact, '|');
+    assert!(!cell_is_free(&m, &Doors::NONE, 3, 2));
+}
+
+#[test]
+fn rays_along_an_axis_graze_the_pillar_edge() {
+    let m = maze("+-----+\n|     |\n|  I  |\n|     |\n+-----+\n");
+    // justo sobre el borde de arriba del pilar: lo roza y lo golpea
+    let edge = 2.5 - PILLAR_HALF;
+    let hit = cast_ray_dir(&m, &Doors::NONE, Vector2::new(1.5, edge), RIGHT).unwrap();
+    assert_eq!((hit.impact, hit.face), ('I', Face::West));
+    assert!((hit.distance - (2.0 - PILLAR_HALF)).abs() < 1e-4, "{}", hit.distance);
+    assert!(hit.tex_u.is_finite());
+
+    // un poco mas afuera pasa de largo
+    let hit = cast_ray_dir(&m, &Doors::NONE, Vector2::new(1.5, edge - 0.01), RIGHT).unwrap();
+    assert_eq!(hit.impact, '|');
+    let hit = cast_ray_dir(&m, &Doors::NONE, Vector2::new(3.5 + PILLAR_HALF + 0.01, 3.5), UP).unwrap();
+    assert_eq!(hit.impact, '-');
+}